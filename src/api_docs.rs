@@ -1,5 +1,5 @@
 use utoipa::OpenApi;
-use crate::models::bible::{BibleMetadata, BibleChapter, Verse, ErrorResponse};
+use crate::models::bible::{BibleMetadata, BookMetadata, ChapterMetadata, Testament, BibleChapter, BibleBook, BibleVersion, Verse, Passage, Comparison, ComparedVerse, VersionError, CacheStats, CircuitBreakerStatus, CircuitState, PrefetchReport, PrefetchFailure, ErrorResponse};
 
 /// OpenAPI documentation for the Bible API
 #[derive(OpenApi)]
@@ -11,13 +11,36 @@ use crate::models::bible::{BibleMetadata, BibleChapter, Verse, ErrorResponse};
     ),
     paths(
         crate::controllers::bible::find,
-        crate::controllers::bible::read
+        crate::controllers::bible::read,
+        crate::controllers::bible::read_verses,
+        crate::controllers::bible::passage,
+        crate::controllers::bible::compare,
+        crate::controllers::bible::list_books,
+        crate::controllers::bible::list_versions,
+        crate::controllers::bible::cache_stats,
+        crate::controllers::bible::upstream_status,
+        crate::controllers::bible::start_prefetch,
+        crate::controllers::bible::prefetch_status
     ),
     components(
         schemas(
             BibleMetadata,
+            BookMetadata,
+            ChapterMetadata,
+            Testament,
             BibleChapter,
+            BibleBook,
+            BibleVersion,
             Verse,
+            Passage,
+            Comparison,
+            ComparedVerse,
+            VersionError,
+            CacheStats,
+            CircuitBreakerStatus,
+            CircuitState,
+            PrefetchReport,
+            PrefetchFailure,
             ErrorResponse
         )
     ),
@@ -36,5 +59,11 @@ impl utoipa::Modify for SecurityAddon {
                 utoipa::openapi::security::ApiKey::Header(utoipa::openapi::security::ApiKeyValue::new("accesskey"))
             )
         );
+        components.add_security_scheme(
+            "adminkey",
+            utoipa::openapi::security::SecurityScheme::ApiKey(
+                utoipa::openapi::security::ApiKey::Header(utoipa::openapi::security::ApiKeyValue::new("adminkey"))
+            )
+        );
     }
 } 
\ No newline at end of file
@@ -1,11 +1,245 @@
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::env;
 use config::{Config, ConfigError, File};
 use lazy_static::lazy_static;
+use crate::import::CorpusFormat;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct BibleConfig {
-    pub base_url: String
+    pub base_url: String,
+    /// Scripture source implementation, e.g. "alkitab_mobi"
+    #[serde(default = "default_source")]
+    pub source: String,
+    /// Per-version overrides of the scripture source, keyed by version code
+    #[serde(default)]
+    pub versions: HashMap<String, VersionSourceConfig>,
+    /// Shared chapter cache settings
+    #[serde(default)]
+    pub cache: CacheConfig,
+    /// Persists scraped chapters on disk when set
+    pub disk_cache: Option<DiskCacheConfig>,
+    /// Retry policy for upstream requests
+    #[serde(default)]
+    pub retry: RetryConfig,
+    /// Circuit breaker guarding upstream requests
+    #[serde(default)]
+    pub circuit_breaker: CircuitBreakerConfig,
+    /// HTTP client used for upstream requests
+    #[serde(default)]
+    pub http: HttpClientConfig,
+    /// Checks on scraped chapters
+    #[serde(default)]
+    pub validation: ValidationConfig,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ValidationConfig {
+    /// Rejects scraped chapters that do not match the expected verses
+    #[serde(default = "default_validation_enabled")]
+    pub enabled: bool,
+    /// Verses a chapter may have more or fewer than expected before it is rejected
+    #[serde(default = "default_verse_count_tolerance")]
+    pub verse_count_tolerance: i32,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        ValidationConfig {
+            enabled: default_validation_enabled(),
+            verse_count_tolerance: default_verse_count_tolerance(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct HttpClientConfig {
+    /// Total time allowed for one request, including reading the body
+    #[serde(default = "default_http_timeout")]
+    pub timeout_seconds: u64,
+    #[serde(default = "default_http_connect_timeout")]
+    pub connect_timeout_seconds: u64,
+    /// Idle connections kept open per host
+    #[serde(default = "default_http_pool_max_idle")]
+    pub pool_max_idle_per_host: usize,
+    /// Seconds an idle pooled connection is kept open
+    #[serde(default = "default_http_pool_idle_timeout")]
+    pub pool_idle_timeout_seconds: u64,
+    #[serde(default = "default_http_user_agent")]
+    pub user_agent: String,
+    /// Proxy URL for all upstream requests, e.g. "http://proxy.internal:3128"
+    pub proxy: Option<String>,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        HttpClientConfig {
+            timeout_seconds: default_http_timeout(),
+            connect_timeout_seconds: default_http_connect_timeout(),
+            pool_max_idle_per_host: default_http_pool_max_idle(),
+            pool_idle_timeout_seconds: default_http_pool_idle_timeout(),
+            user_agent: default_http_user_agent(),
+            proxy: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive upstream failures that open the circuit
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+    /// Seconds the circuit stays open before a probe request is let through
+    #[serde(default = "default_cool_down")]
+    pub cool_down_seconds: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        CircuitBreakerConfig {
+            failure_threshold: default_failure_threshold(),
+            cool_down_seconds: default_cool_down(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct RetryConfig {
+    /// Total attempts per upstream request, including the first one
+    #[serde(default = "default_retry_attempts")]
+    pub max_attempts: u32,
+    /// Pause after the first failed attempt, doubled after every further failure
+    #[serde(default = "default_retry_base_delay")]
+    pub base_delay_ms: u64,
+    /// Longest pause between two attempts
+    #[serde(default = "default_retry_max_delay")]
+    pub max_delay_ms: u64,
+    /// Fraction of each pause that is randomised, between 0 and 1
+    #[serde(default = "default_retry_jitter")]
+    pub jitter: f64,
+    /// HTTP status codes worth retrying; network errors and timeouts are always retried
+    #[serde(default = "default_retryable_statuses")]
+    pub retryable_statuses: Vec<u16>,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_attempts: default_retry_attempts(),
+            base_delay_ms: default_retry_base_delay(),
+            max_delay_ms: default_retry_max_delay(),
+            jitter: default_retry_jitter(),
+            retryable_statuses: default_retryable_statuses(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct DiskCacheConfig {
+    /// Directory holding the cached chapter files
+    pub path: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CacheConfig {
+    /// Seconds a cached chapter stays fresh before it is served stale and refreshed; 0 disables the cache
+    #[serde(default = "default_cache_ttl")]
+    pub ttl_seconds: u64,
+    /// Maximum number of cached chapters; 0 disables the cache
+    #[serde(default = "default_cache_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        CacheConfig {
+            ttl_seconds: default_cache_ttl(),
+            max_entries: default_cache_max_entries(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct VersionSourceConfig {
+    /// "alkitab_mobi" or "local"
+    pub source: String,
+    /// Corpus file or directory for the "local" source
+    pub path: Option<String>,
+    /// Format of the corpus files, e.g. "json" or "usfm"
+    #[serde(default)]
+    pub format: CorpusFormat,
+    /// Version name reported for chapters of formats that do not carry one
+    pub name: Option<String>,
+    /// HTTP client settings for this version's remote source, replacing `[bible.http]`
+    pub http: Option<HttpClientConfig>,
+}
+
+fn default_source() -> String {
+    "alkitab_mobi".to_string()
+}
+
+fn default_cache_ttl() -> u64 {
+    3600
+}
+
+fn default_cache_max_entries() -> usize {
+    1000
+}
+
+fn default_retry_attempts() -> u32 {
+    3
+}
+
+fn default_retry_base_delay() -> u64 {
+    200
+}
+
+fn default_retry_max_delay() -> u64 {
+    2000
+}
+
+fn default_retry_jitter() -> f64 {
+    0.5
+}
+
+fn default_retryable_statuses() -> Vec<u16> {
+    vec![408, 429, 500, 502, 503, 504]
+}
+
+fn default_http_timeout() -> u64 {
+    10
+}
+
+fn default_http_connect_timeout() -> u64 {
+    5
+}
+
+fn default_http_pool_max_idle() -> usize {
+    10
+}
+
+fn default_http_pool_idle_timeout() -> u64 {
+    30
+}
+
+fn default_http_user_agent() -> String {
+    format!("alkitab-api-rust/{}", env!("CARGO_PKG_VERSION"))
+}
+
+fn default_validation_enabled() -> bool {
+    true
+}
+
+fn default_verse_count_tolerance() -> i32 {
+    2
+}
+
+fn default_failure_threshold() -> u32 {
+    5
+}
+
+fn default_cool_down() -> u64 {
+    30
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -1,49 +1,93 @@
-use actix_web::{get, web, HttpResponse};
+use actix_web::{get, post, web, HttpResponse};
 #[allow(unused_imports)]
-use crate::models::bible::{BibleMetadata, BibleChapter, ErrorResponse};
+use crate::models::bible::{BibleBook, BibleMetadata, BookMetadata, BibleChapter, BibleVersion, CacheStats, CircuitBreakerStatus, ErrorResponse, Comparison, Passage, PrefetchReport};
 use crate::services::bible;
 use crate::error::AppError;
+use crate::import::osis::write_osis;
+use crate::services::cache::CacheStatus;
 use serde::Deserialize;
+const CACHE_STATUS_HEADER: &str = "X-Cache-Status";
+
 #[derive(Deserialize)]
 pub struct QueryParams {
     version: Option<String>,
+    format: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct FindQueryParams {
+    version: Option<String>,
+    api_version: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CompareQueryParams {
+    versions: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BooksQueryParams {
+    testament: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct PrefetchQueryParams {
+    version: Option<String>,
+    concurrency: Option<usize>,
+    delay_ms: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct PassageQueryParams {
+    #[serde(rename = "ref")]
+    reference: String,
+    version: Option<String>,
 }
 
 #[utoipa::path(
     get,
     path = "/bible/find/{book}",
     responses(
-        (status = 200, description = "Get Bible book metadata", body = BibleMetadata),
+        (status = 200, description = "Get Bible book metadata; BookMetadata with api_version=2", body = BibleMetadata),
         (status = 400, description = "Bad Request", body = ErrorResponse),
         (status = 404, description = "Not Found", body = ErrorResponse),
         (status = 500, description = "Internal Server Error", body = ErrorResponse)
     ),
     params(
-        ("book" = String, Path, description = "Book name")
+        ("book" = String, Path, description = "Book name"),
+        ("version" = Option<String>, Query, description = "Version whose verse counts to report, default tb"),
+        ("api_version" = Option<String>, Query, description = "Response shape: 1 (default) for BibleMetadata, 2 for BookMetadata")
     ),
     security(
         ("accesskey" = [])
     )
 )]
 #[get("/find/{book}")]
-pub async fn find(path: web::Path<String>) -> Result<HttpResponse, AppError> {
+pub async fn find(path: web::Path<String>, query: web::Query<FindQueryParams>) -> Result<HttpResponse, AppError> {
     let book = path.into_inner().trim().to_string();
-    let result = bible::find(&book).await?;
-    Ok(HttpResponse::Ok().json(result))
+    let version = query.version.as_deref().unwrap_or("tb");
+    let result = bible::find(&book, version).await?;
+    match query.api_version.as_deref().unwrap_or("1") {
+        "1" => Ok(HttpResponse::Ok().json(BibleMetadata::from(&result))),
+        "2" => Ok(HttpResponse::Ok().json(result)),
+        other => Err(AppError::InvalidInput(format!("Unsupported api_version '{}', expected 1 or 2", other))),
+    }
 }
 
 #[utoipa::path(
     get,
     path = "/bible/read/{book}/{chapter}",
     responses(
-        (status = 200, description = "Get Bible chapter content", body = BibleChapter),
+        (status = 200, description = "Get Bible chapter content", body = BibleChapter,
+            headers(("X-Cache-Status" = String, description = "hit, miss, or stale when served from an expired cache entry"))),
         (status = 400, description = "Bad Request", body = ErrorResponse),
         (status = 404, description = "Not Found", body = ErrorResponse),
         (status = 500, description = "Internal Server Error", body = ErrorResponse)
     ),
     params(
         ("book" = String, Path, description = "Book name"),
-        ("chapter" = i32, Path, description = "Chapter number")
+        ("chapter" = i32, Path, description = "Chapter number"),
+        ("format" = Option<String>, Query, description = "Response format: json (default) or osis")
     ),
     security(
         ("accesskey" = [])
@@ -57,6 +101,210 @@ pub async fn read(
     let (book, chapter) = path.into_inner();
     let book = book.trim().to_string();
     let version = query.version.as_deref().unwrap_or("tb");
-    let result = bible::read(&book, chapter, version).await?;
+    let (result, status) = bible::read(&book, chapter, version).await?;
+    chapter_response(result, status, version, query.format.as_deref())
+}
+
+#[utoipa::path(
+    get,
+    path = "/bible/read/{book}/{chapter}/{verses}",
+    responses(
+        (status = 200, description = "Get selected verses of a Bible chapter", body = BibleChapter,
+            headers(("X-Cache-Status" = String, description = "hit, miss, or stale when served from an expired cache entry"))),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    params(
+        ("book" = String, Path, description = "Book name"),
+        ("chapter" = i32, Path, description = "Chapter number"),
+        ("verses" = String, Path, description = "Verse selection, e.g. 16, 16-18 or 1-3,7,10-12"),
+        ("format" = Option<String>, Query, description = "Response format: json (default) or osis")
+    ),
+    security(
+        ("accesskey" = [])
+    )
+)]
+#[get("/read/{book}/{chapter}/{verses}")]
+pub async fn read_verses(
+    path: web::Path<(String, i32, String)>,
+    query: web::Query<QueryParams>,
+) -> Result<HttpResponse, AppError> {
+    let (book, chapter, verses) = path.into_inner();
+    let book = book.trim().to_string();
+    let version = query.version.as_deref().unwrap_or("tb");
+    let (result, status) = bible::read_verses(&book, chapter, &verses, version).await?;
+    chapter_response(result, status, version, query.format.as_deref())
+}
+
+#[utoipa::path(
+    get,
+    path = "/bible/passage",
+    responses(
+        (status = 200, description = "Get the passages for a free-text scripture reference", body = [Passage]),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    params(
+        ("ref" = String, Query, description = "Scripture reference, e.g. \"Yoh 3:16-18; Mzm 23\""),
+        ("version" = Option<String>, Query, description = "Bible translation version (default: tb)")
+    ),
+    security(
+        ("accesskey" = [])
+    )
+)]
+#[get("/passage")]
+pub async fn passage(query: web::Query<PassageQueryParams>) -> Result<HttpResponse, AppError> {
+    let version = query.version.as_deref().unwrap_or("tb");
+    let result = bible::passage(&query.reference, version).await?;
     Ok(HttpResponse::Ok().json(result))
+}
+
+#[utoipa::path(
+    get,
+    path = "/bible/compare/{book}/{chapter}",
+    responses(
+        (status = 200, description = "Get a chapter in several versions with the verses aligned by number; versions that fail are listed in errors", body = Comparison),
+        (status = 400, description = "Bad Request", body = ErrorResponse),
+        (status = 404, description = "Not Found", body = ErrorResponse),
+        (status = 500, description = "Internal Server Error", body = ErrorResponse)
+    ),
+    params(
+        ("book" = String, Path, description = "Book name"),
+        ("chapter" = i32, Path, description = "Chapter number"),
+        ("versions" = Option<String>, Query, description = "Comma-separated versions to compare, e.g. tb,ayt,esv (default: tb)")
+    ),
+    security(
+        ("accesskey" = [])
+    )
+)]
+#[get("/compare/{book}/{chapter}")]
+pub async fn compare(
+    path: web::Path<(String, i32)>,
+    query: web::Query<CompareQueryParams>,
+) -> Result<HttpResponse, AppError> {
+    let (book, chapter) = path.into_inner();
+    let versions = query.versions.as_deref().unwrap_or("tb");
+    let result = bible::compare(book.trim(), chapter, versions).await?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+#[utoipa::path(
+    get,
+    path = "/bible/books",
+    responses(
+        (status = 200, description = "List the books in canonical order with their names, abbreviations and chapter counts", body = [BibleBook]),
+        (status = 400, description = "Bad Request", body = ErrorResponse)
+    ),
+    params(
+        ("testament" = Option<String>, Query, description = "Only books of this testament: old or new")
+    ),
+    security(
+        ("accesskey" = [])
+    )
+)]
+#[get("/books")]
+pub async fn list_books(query: web::Query<BooksQueryParams>) -> Result<HttpResponse, AppError> {
+    Ok(HttpResponse::Ok().json(bible::books(query.testament.as_deref())?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/bible/versions",
+    responses(
+        (status = 200, description = "List the versions that can be passed as ?version=", body = [BibleVersion])
+    ),
+    security(
+        ("accesskey" = [])
+    )
+)]
+#[get("/versions")]
+pub async fn list_versions() -> Result<HttpResponse, AppError> {
+    Ok(HttpResponse::Ok().json(bible::versions()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/bible/cache/stats",
+    responses(
+        (status = 200, description = "Get chapter cache hit and miss counters", body = CacheStats)
+    ),
+    security(
+        ("accesskey" = [])
+    )
+)]
+#[get("/cache/stats")]
+pub async fn cache_stats() -> Result<HttpResponse, AppError> {
+    Ok(HttpResponse::Ok().json(bible::cache_stats()))
+}
+
+#[utoipa::path(
+    get,
+    path = "/bible/status",
+    responses(
+        (status = 200, description = "Get the state of the circuit breaker guarding the upstream", body = CircuitBreakerStatus)
+    ),
+    security(
+        ("accesskey" = [])
+    )
+)]
+#[get("/status")]
+pub async fn upstream_status() -> Result<HttpResponse, AppError> {
+    Ok(HttpResponse::Ok().json(bible::upstream_status()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/bible/admin/prefetch",
+    responses(
+        (status = 202, description = "Start fetching every chapter of a version in the background", body = PrefetchReport),
+        (status = 400, description = "Bad Request, e.g. a prefetch is already running", body = ErrorResponse)
+    ),
+    params(
+        ("version" = Option<String>, Query, description = "Bible translation version (default: tb)"),
+        ("concurrency" = Option<usize>, Query, description = "Chapters fetched at once (default: 4, at most 16)"),
+        ("delay_ms" = Option<u64>, Query, description = "Pause before each upstream request in milliseconds (default: 250, at least 100)")
+    ),
+    security(
+        ("adminkey" = [])
+    )
+)]
+#[post("/prefetch")]
+pub async fn start_prefetch(query: web::Query<PrefetchQueryParams>) -> Result<HttpResponse, AppError> {
+    let version = query.version.as_deref().unwrap_or("tb");
+    let result = bible::start_prefetch(version, query.concurrency, query.delay_ms)?;
+    Ok(HttpResponse::Accepted().json(result))
+}
+
+#[utoipa::path(
+    get,
+    path = "/bible/admin/prefetch",
+    responses(
+        (status = 200, description = "Get the progress and per-chapter failures of the latest prefetch", body = PrefetchReport),
+        (status = 404, description = "No prefetch has been started", body = ErrorResponse)
+    ),
+    security(
+        ("adminkey" = [])
+    )
+)]
+#[get("/prefetch")]
+pub async fn prefetch_status() -> Result<HttpResponse, AppError> {
+    let result = bible::prefetch_status()?;
+    Ok(HttpResponse::Ok().json(result))
+}
+
+/// Renders a chapter as JSON or, with `?format=osis`, as an OSIS XML document.
+/// `X-Cache-Status` tells clients whether the chapter was served stale.
+fn chapter_response(chapter: BibleChapter, status: CacheStatus, version: &str, format: Option<&str>) -> Result<HttpResponse, AppError> {
+    let mut response = HttpResponse::Ok();
+    response.insert_header((CACHE_STATUS_HEADER, status.as_str()));
+    match format.unwrap_or("json") {
+        "json" => Ok(response.json(chapter)),
+        "osis" => {
+            let document = write_osis(&[chapter], &version.to_uppercase())?;
+            Ok(response.content_type("application/xml").body(document))
+        }
+        other => Err(AppError::InvalidInput(format!("Unsupported format '{}', expected json or osis", other))),
+    }
 }
\ No newline at end of file
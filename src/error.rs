@@ -1,8 +1,9 @@
+use actix_web::http::header::RETRY_AFTER;
 use actix_web::{HttpResponse, ResponseError};
 use serde_json::json;
 use thiserror::Error;
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum AppError {
     #[error("Failed to fetch data from external source: {0}")]
     ExternalApiError(String),
@@ -18,6 +19,12 @@ pub enum AppError {
 
     #[error("Resource not found: {0}")]
     NotFound(String),
+
+    #[error("Unexpected upstream content: {0}")]
+    ParserDrift(String),
+
+    #[error("Service unavailable: {message}")]
+    ServiceUnavailable { message: String, retry_after_secs: u64 },
 }
 
 impl ResponseError for AppError {
@@ -53,6 +60,20 @@ impl ResponseError for AppError {
                     "message": msg
                 }))
             }
+            AppError::ParserDrift(msg) => {
+                HttpResponse::BadGateway().json(json!({
+                    "error": "Parser Drift",
+                    "message": msg
+                }))
+            }
+            AppError::ServiceUnavailable { message, retry_after_secs } => {
+                HttpResponse::ServiceUnavailable()
+                    .insert_header((RETRY_AFTER, retry_after_secs.to_string()))
+                    .json(json!({
+                        "error": "Service Unavailable",
+                        "message": message
+                    }))
+            }
         }
     }
 } 
\ No newline at end of file
@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use csv::{ReaderBuilder, StringRecord};
+use crate::import::{resolve_book_name, ChapterBuilder, ImportError, ImportErrorKind};
+use crate::models::bible::BibleChapter;
+
+/// Parses `book,chapter,verse,text` rows into chapters.
+///
+/// A leading `book,chapter,verse,text` header row is optional. Book identifiers are
+/// resolved with [`Book::resolve`](crate::models::book::Book::resolve), so English and Indonesian names or
+/// abbreviations all work. Text containing commas may be quoted; unquoted extra
+/// fields are joined back into the verse text. Rows may come in any order, but
+/// chapters are returned in the order they first appear.
+pub fn parse_csv(input: &str, file: &str, version: Option<&str>) -> Result<Vec<BibleChapter>, ImportError> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(input.as_bytes());
+
+    let mut builders: Vec<ChapterBuilder> = Vec::new();
+    let mut index: HashMap<(&'static str, i32), usize> = HashMap::new();
+
+    for (row, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| ImportError {
+            file: file.to_string(),
+            line: e.position().map_or(row + 1, |p| p.line() as usize),
+            kind: ImportErrorKind::Malformed(e.to_string()),
+        })?;
+        let line = record.position().map_or(row + 1, |p| p.line() as usize);
+        let error = |kind| ImportError { file: file.to_string(), line, kind };
+
+        if record.iter().all(|field| field.trim().is_empty()) || row == 0 && is_header(&record) {
+            continue;
+        }
+        if record.len() < 4 {
+            return Err(error(ImportErrorKind::Malformed(format!(
+                "expected book,chapter,verse,text but found {} fields", record.len()
+            ))));
+        }
+
+        let book = resolve_book_name(&record[0])
+            .ok_or_else(|| error(ImportErrorKind::UnknownBook(record[0].trim().to_string())))?;
+        let chapter = record[1].trim().parse::<i32>()
+            .ok()
+            .filter(|c| *c > 0)
+            .ok_or_else(|| error(ImportErrorKind::InvalidChapter(record[1].trim().to_string())))?;
+        let verse = record[2].trim().parse::<i32>()
+            .ok()
+            .filter(|v| *v > 0)
+            .ok_or_else(|| error(ImportErrorKind::InvalidVerse(record[2].trim().to_string())))?;
+        let text = record.iter().skip(3).collect::<Vec<&str>>().join(",");
+
+        let position = *index.entry((book, chapter)).or_insert_with(|| {
+            builders.push(ChapterBuilder::new(book, chapter, version));
+            builders.len() - 1
+        });
+        builders[position].verse(verse, &text).map_err(error)?;
+    }
+
+    Ok(builders.into_iter().filter_map(ChapterBuilder::finish).collect())
+}
+
+fn is_header(record: &StringRecord) -> bool {
+    record.get(0).is_some_and(|field| field.trim().eq_ignore_ascii_case("book"))
+}
@@ -0,0 +1,259 @@
+pub mod csv;
+pub mod osis;
+pub mod usfm;
+pub mod zefania;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::{BytesRef, BytesStart};
+use serde::Deserialize;
+use thiserror::Error;
+use crate::error::AppError;
+use crate::models::bible::{BibleChapter, Verse};
+use crate::models::book::Book;
+
+/// File formats a local corpus can be loaded from
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum CorpusFormat {
+    /// An array of chapters in the `/bible/read` response shape
+    #[default]
+    Json,
+    /// Unified Standard Format Markers, one book per file
+    Usfm,
+    /// OSIS XML, with container or milestone verses
+    Osis,
+    /// Zefania XML
+    Zefania,
+    /// Plain `book,chapter,verse,text` rows
+    Csv,
+}
+
+impl CorpusFormat {
+    /// File extensions picked up when a corpus path is a directory
+    pub fn extensions(&self) -> &'static [&'static str] {
+        match self {
+            CorpusFormat::Json => &["json"],
+            CorpusFormat::Usfm => &["usfm", "sfm"],
+            CorpusFormat::Osis => &["xml", "osis"],
+            CorpusFormat::Zefania => &["xml"],
+            CorpusFormat::Csv => &["csv"],
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ImportErrorKind {
+    #[error("unknown book '{0}'")]
+    UnknownBook(String),
+
+    #[error("non-numeric chapter '{0}'")]
+    InvalidChapter(String),
+
+    #[error("non-numeric verse '{0}'")]
+    InvalidVerse(String),
+
+    #[error("duplicate verse {0}")]
+    DuplicateVerse(String),
+
+    #[error("{0}")]
+    Malformed(String),
+}
+
+/// A problem found while importing a corpus file, with the line it was found on
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{file}:{line}: {kind}")]
+pub struct ImportError {
+    pub file: String,
+    pub line: usize,
+    pub kind: ImportErrorKind,
+}
+
+impl From<ImportError> for AppError {
+    fn from(error: ImportError) -> Self {
+        AppError::InvalidInput(format!("Invalid corpus {}", error))
+    }
+}
+
+/// Loads every chapter from a corpus file, or from all matching files in a directory.
+///
+/// Every file is validated before giving up, so a directory with several broken
+/// files reports the first problem in each of them.
+pub fn load_corpus(path: &Path, format: CorpusFormat, version_name: Option<&str>) -> Result<Vec<BibleChapter>, AppError> {
+    let mut chapters = Vec::new();
+    let mut errors: Vec<ImportError> = Vec::new();
+    for file in corpus_files(path, format)? {
+        let content = fs::read_to_string(&file)
+            .map_err(|e| AppError::NotFound(format!("Could not read corpus {}: {}", file.display(), e)))?;
+        let file_name = file.display().to_string();
+        let parsed = match format {
+            CorpusFormat::Json => parse_json(&content, &file_name),
+            CorpusFormat::Usfm => usfm::parse_usfm(&content, &file_name, version_name),
+            CorpusFormat::Osis => osis::parse_osis(&content, &file_name, version_name),
+            CorpusFormat::Zefania => zefania::parse_zefania(&content, &file_name, version_name),
+            CorpusFormat::Csv => self::csv::parse_csv(&content, &file_name, version_name),
+        };
+        match parsed {
+            Ok(parsed) => chapters.extend(parsed),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    match errors.len() {
+        0 => Ok(chapters),
+        1 => Err(errors.remove(0).into()),
+        count => {
+            let details: Vec<String> = errors.iter().map(ImportError::to_string).collect();
+            Err(AppError::InvalidInput(format!(
+                "Invalid corpus, {} files failed: {}", count, details.join("; ")
+            )))
+        }
+    }
+}
+
+/// Resolves a book identifier found in a corpus file, e.g. "Genesis", "Kej" or "Kejadian"
+pub(crate) fn resolve_book_name(identifier: &str) -> Option<&'static str> {
+    Book::resolve(identifier).ok().map(|book| book.indonesian)
+}
+
+fn corpus_files(path: &Path, format: CorpusFormat) -> Result<Vec<PathBuf>, AppError> {
+    if !path.is_dir() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let entries = fs::read_dir(path)
+        .map_err(|e| AppError::NotFound(format!("Could not read corpus directory {}: {}", path.display(), e)))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|file| {
+            file.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| format.extensions().iter().any(|e| e.eq_ignore_ascii_case(ext)))
+        })
+        .collect();
+    files.sort();
+
+    if files.is_empty() {
+        return Err(AppError::NotFound(format!("No {:?} corpus files found in {}", format, path.display())));
+    }
+    Ok(files)
+}
+
+fn parse_json(content: &str, file: &str) -> Result<Vec<BibleChapter>, ImportError> {
+    serde_json::from_str(content).map_err(|e| ImportError {
+        file: file.to_string(),
+        line: e.line(),
+        kind: ImportErrorKind::Malformed(e.to_string()),
+    })
+}
+
+/// Collects the verses and section headings of one chapter, producing `title`
+/// ranges the same way the alkitab.mobi scraper does for TB
+pub(crate) struct ChapterBuilder {
+    book: &'static str,
+    chapter: i32,
+    version: Option<String>,
+    verses: Vec<Verse>,
+    titles: Vec<String>,
+    current_title: Option<String>,
+    current_start_verse: Option<i32>,
+    last_verse_number: Option<i32>,
+}
+
+impl ChapterBuilder {
+    pub(crate) fn new(book: &'static str, chapter: i32, version: Option<&str>) -> Self {
+        ChapterBuilder {
+            book,
+            chapter,
+            version: version.map(str::to_string),
+            verses: Vec::new(),
+            titles: Vec::new(),
+            current_title: None,
+            current_start_verse: None,
+            last_verse_number: None,
+        }
+    }
+
+    pub(crate) fn heading(&mut self, title: &str) {
+        let title = title.split_whitespace().collect::<Vec<&str>>().join(" ");
+        if title.is_empty() {
+            return;
+        }
+        if let (Some(prev_title), Some(start_verse)) = (self.current_title.take(), self.current_start_verse.take())
+            && let Some(last_verse) = self.last_verse_number
+        {
+            self.titles.push(format!("{} ({}-{})", prev_title, start_verse, last_verse));
+        }
+        self.current_title = Some(title);
+    }
+
+    pub(crate) fn verse(&mut self, number: i32, content: &str) -> Result<(), ImportErrorKind> {
+        let content = content.split_whitespace().collect::<Vec<&str>>().join(" ");
+        if content.is_empty() {
+            return Ok(());
+        }
+        if self.verses.iter().any(|v| v.verse == number) {
+            return Err(ImportErrorKind::DuplicateVerse(format!("{} {}:{}", self.book, self.chapter, number)));
+        }
+        if self.current_start_verse.is_none() {
+            self.current_start_verse = Some(number);
+        }
+        self.verses.push(Verse { verse: number, content });
+        self.last_verse_number = Some(number);
+        Ok(())
+    }
+
+    /// Removes a heading that has not been followed by any verse yet, so it can
+    /// be carried over to the next chapter
+    pub(crate) fn take_pending_heading(&mut self) -> Option<String> {
+        if self.current_start_verse.is_none() {
+            self.current_title.take()
+        } else {
+            None
+        }
+    }
+
+    /// Finishes the chapter, or returns `None` if it holds no verses
+    pub(crate) fn finish(mut self) -> Option<BibleChapter> {
+        if self.verses.is_empty() {
+            return None;
+        }
+        if let (Some(title), Some(start_verse), Some(last_verse)) =
+            (self.current_title, self.current_start_verse, self.last_verse_number)
+        {
+            self.titles.push(format!("{} ({}-{})", title, start_verse, last_verse));
+        }
+
+        Some(BibleChapter {
+            book: vec![self.book.to_string()],
+            chapter: self.chapter,
+            title: self.titles,
+            total_verses: self.verses.len(),
+            version: self.version,
+            verses: self.verses,
+        })
+    }
+}
+
+/// Reads an XML attribute, unescaping its value
+pub(crate) fn xml_attribute(element: &BytesStart, name: &str) -> Result<Option<String>, ImportErrorKind> {
+    let attribute = element.try_get_attribute(name)
+        .map_err(|e| ImportErrorKind::Malformed(e.to_string()))?;
+    match attribute {
+        Some(attribute) => attribute.unescape_value()
+            .map(|value| Some(value.into_owned()))
+            .map_err(|e| ImportErrorKind::Malformed(e.to_string())),
+        None => Ok(None),
+    }
+}
+
+/// Resolves a character reference or one of the predefined XML entities, such as `&amp;`
+pub(crate) fn xml_reference(reference: &BytesRef) -> Result<String, ImportErrorKind> {
+    let resolved = if reference.is_char_ref() {
+        reference.resolve_char_ref().ok().flatten().map(String::from)
+    } else {
+        reference.decode().ok().and_then(|name| resolve_predefined_entity(&name)).map(str::to_string)
+    };
+    resolved.ok_or_else(|| ImportErrorKind::Malformed("unknown entity reference".to_string()))
+}
@@ -0,0 +1,336 @@
+use std::io::Cursor;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use crate::error::AppError;
+use crate::import::{xml_attribute, xml_reference, ChapterBuilder, ImportError, ImportErrorKind};
+use crate::models::bible::{parse_title_range, BibleChapter};
+use crate::models::book::Book;
+
+const OSIS_NAMESPACE: &str = "http://www.bibletechnologies.net/2003/OSIS/namespace";
+
+/// Title types that are not section headings
+const SKIPPED_TITLE_TYPES: [&str; 4] = ["main", "chapter", "psalm", "acrostic"];
+
+struct Reading<'a> {
+    input: &'a str,
+    file: &'a str,
+    version: Option<&'a str>,
+    chapter: Option<(&'static str, i32, ChapterBuilder)>,
+    verse: Option<(i32, String)>,
+    heading: Option<String>,
+    pending_heading: Option<String>,
+    chapters: Vec<BibleChapter>,
+}
+
+/// Parses an OSIS XML document into chapters.
+///
+/// Both container (`<verse osisID="Gen.1.1">…</verse>`) and milestone
+/// (`<verse sID="Gen.1.1"/>…<verse eID="Gen.1.1"/>`) verses are supported. Section
+/// `<title>`s become `title` ranges like the TB scraper produces; notes are dropped.
+pub fn parse_osis(input: &str, file: &str, version: Option<&str>) -> Result<Vec<BibleChapter>, ImportError> {
+    let mut reading = Reading {
+        input,
+        file,
+        version,
+        chapter: None,
+        verse: None,
+        heading: None,
+        pending_heading: None,
+        chapters: Vec::new(),
+    };
+    let mut reader = Reader::from_str(input);
+    let mut skip_depth = 0usize;
+
+    loop {
+        let position = reader.buffer_position() as usize;
+        let event = reader.read_event()
+            .map_err(|e| reading.error(position, ImportErrorKind::Malformed(e.to_string())))?;
+
+        match event {
+            Event::Eof => break,
+            Event::Start(element) => {
+                if skip_depth > 0 {
+                    skip_depth += 1;
+                    continue;
+                }
+                match element.local_name().as_ref() {
+                    b"note" => skip_depth = 1,
+                    b"title" => {
+                        let title_type = reading.attribute(&element, "type", position)?;
+                        let canonical = reading.attribute(&element, "canonical", position)?;
+                        // Untyped titles outside a chapter are book or header titles
+                        if title_type.as_deref().is_some_and(|t| SKIPPED_TITLE_TYPES.contains(&t))
+                            || canonical.as_deref() == Some("true")
+                            || reading.chapter.is_none() && title_type.is_none()
+                        {
+                            skip_depth = 1;
+                        } else {
+                            reading.finish_verse(position)?;
+                            reading.heading = Some(String::new());
+                        }
+                    }
+                    b"chapter" => reading.chapter_start(&element, position)?,
+                    b"verse" => reading.verse_start(&element, position)?,
+                    _ => {}
+                }
+            }
+            Event::Empty(element) => {
+                if skip_depth > 0 {
+                    continue;
+                }
+                match element.local_name().as_ref() {
+                    b"chapter" => {
+                        if reading.attribute(&element, "eID", position)?.is_some() {
+                            reading.finish_chapter(position)?;
+                        } else {
+                            reading.chapter_start(&element, position)?;
+                        }
+                    }
+                    b"verse" => {
+                        if reading.attribute(&element, "eID", position)?.is_some() {
+                            reading.finish_verse(position)?;
+                        } else {
+                            reading.verse_start(&element, position)?;
+                        }
+                    }
+                    b"lb" => reading.push_text(" "),
+                    _ => {}
+                }
+            }
+            Event::End(element) => {
+                if skip_depth > 0 {
+                    skip_depth -= 1;
+                    continue;
+                }
+                match element.local_name().as_ref() {
+                    b"title" => reading.finish_heading(),
+                    b"verse" => reading.finish_verse(position)?,
+                    b"chapter" => reading.finish_chapter(position)?,
+                    b"l" | b"p" | b"lg" => reading.push_text(" "),
+                    _ => {}
+                }
+            }
+            Event::Text(text) if skip_depth == 0 => {
+                let text = text.decode()
+                    .map_err(|e| reading.error(position, ImportErrorKind::Malformed(e.to_string())))?;
+                reading.push_text(&text);
+            }
+            Event::CData(text) if skip_depth == 0 => {
+                let text = text.decode()
+                    .map_err(|e| reading.error(position, ImportErrorKind::Malformed(e.to_string())))?;
+                reading.push_text(&text);
+            }
+            Event::GeneralRef(reference) if skip_depth == 0 => {
+                let resolved = xml_reference(&reference).map_err(|kind| reading.error(position, kind))?;
+                reading.push_text(&resolved);
+            }
+            _ => {}
+        }
+    }
+
+    reading.finish_chapter(input.len())?;
+    Ok(reading.chapters)
+}
+
+impl Reading<'_> {
+    fn attribute(&self, element: &BytesStart, name: &str, position: usize) -> Result<Option<String>, ImportError> {
+        xml_attribute(element, name).map_err(|kind| self.error(position, kind))
+    }
+
+    /// Resolves an osisID such as "Gen.1.1" into a book and its numeric parts
+    fn parse_osis_id(&self, osis_id: &str, position: usize) -> Result<(&'static str, i32, Option<i32>), ImportError> {
+        // Bridged verses list several ids, e.g. "Gen.1.1 Gen.1.2"; the first one wins
+        let osis_id = osis_id.split_whitespace().next().unwrap_or_default();
+        let mut parts = osis_id.split('.');
+        let book_id = parts.next().unwrap_or_default();
+        let book = Book::from_osis(book_id)
+            .map(|book| book.indonesian)
+            .ok_or_else(|| self.error(position, ImportErrorKind::UnknownBook(book_id.to_string())))?;
+        let chapter = parts.next().unwrap_or_default();
+        let chapter = chapter.parse::<i32>()
+            .ok()
+            .filter(|c| *c > 0)
+            .ok_or_else(|| self.error(position, ImportErrorKind::InvalidChapter(chapter.to_string())))?;
+        let verse = match parts.next() {
+            Some(verse) => Some(verse.parse::<i32>()
+                .ok()
+                .filter(|v| *v > 0)
+                .ok_or_else(|| self.error(position, ImportErrorKind::InvalidVerse(verse.to_string())))?),
+            None => None,
+        };
+        Ok((book, chapter, verse))
+    }
+
+    fn chapter_start(&mut self, element: &BytesStart, position: usize) -> Result<(), ImportError> {
+        let osis_id = match self.attribute(element, "osisID", position)? {
+            Some(id) => id,
+            None => match self.attribute(element, "sID", position)? {
+                Some(id) => id,
+                None => return Ok(()),
+            },
+        };
+        let (book, chapter, _) = self.parse_osis_id(&osis_id, position)?;
+        self.enter_chapter(book, chapter, position)
+    }
+
+    fn enter_chapter(&mut self, book: &'static str, chapter: i32, position: usize) -> Result<(), ImportError> {
+        if let Some((current_book, current_chapter, _)) = &self.chapter
+            && *current_book == book
+            && *current_chapter == chapter
+        {
+            return Ok(());
+        }
+        self.finish_chapter(position)?;
+        let mut builder = ChapterBuilder::new(book, chapter, self.version);
+        if let Some(heading) = self.pending_heading.take() {
+            builder.heading(&heading);
+        }
+        self.chapter = Some((book, chapter, builder));
+        Ok(())
+    }
+
+    fn verse_start(&mut self, element: &BytesStart, position: usize) -> Result<(), ImportError> {
+        let osis_id = match self.attribute(element, "osisID", position)? {
+            Some(id) => id,
+            None => match self.attribute(element, "sID", position)? {
+                Some(id) => id,
+                None => return Ok(()),
+            },
+        };
+        let (book, chapter, verse) = self.parse_osis_id(&osis_id, position)?;
+        let verse = verse
+            .ok_or_else(|| self.error(position, ImportErrorKind::InvalidVerse(osis_id.clone())))?;
+        self.finish_verse(position)?;
+        self.enter_chapter(book, chapter, position)?;
+        self.verse = Some((verse, String::new()));
+        Ok(())
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if let Some(heading) = self.heading.as_mut() {
+            heading.push_str(text);
+        } else if let Some((_, content)) = self.verse.as_mut() {
+            content.push_str(text);
+        }
+    }
+
+    fn finish_heading(&mut self) {
+        let Some(heading) = self.heading.take() else {
+            return;
+        };
+        match self.chapter.as_mut() {
+            Some((_, _, chapter)) => chapter.heading(&heading),
+            None => self.pending_heading = Some(heading),
+        }
+    }
+
+    fn finish_verse(&mut self, position: usize) -> Result<(), ImportError> {
+        if let Some((number, content)) = self.verse.take()
+            && let Some((_, _, chapter)) = self.chapter.as_mut()
+        {
+            chapter.verse(number, &content).map_err(|kind| self.error(position, kind))?;
+        }
+        Ok(())
+    }
+
+    fn finish_chapter(&mut self, position: usize) -> Result<(), ImportError> {
+        self.finish_verse(position)?;
+        if let Some((_, _, mut chapter)) = self.chapter.take() {
+            if let Some(heading) = chapter.take_pending_heading() {
+                self.pending_heading = Some(heading);
+            }
+            self.chapters.extend(chapter.finish());
+        }
+        Ok(())
+    }
+
+    fn error(&self, position: usize, kind: ImportErrorKind) -> ImportError {
+        ImportError {
+            file: self.file.to_string(),
+            line: self.input[..position.min(self.input.len())].matches('\n').count() + 1,
+            kind,
+        }
+    }
+}
+
+/// Serialises chapters as an OSIS document.
+///
+/// Section titles are written as `<title type="section">` before the verse their
+/// range starts at, so reading the document back yields the same `title` list.
+/// `work` is the OSIS work identifier, e.g. "TB".
+pub fn write_osis(chapters: &[BibleChapter], work: &str) -> Result<String, AppError> {
+    write_document(chapters, work)
+        .map_err(|e| AppError::InvalidInput(format!("Could not write OSIS document: {}", e)))
+}
+
+fn write_document(chapters: &[BibleChapter], work: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let mut writer = Writer::new_with_indent(Cursor::new(Vec::new()), b' ', 2);
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+
+    let mut osis = BytesStart::new("osis");
+    osis.push_attribute(("xmlns", OSIS_NAMESPACE));
+    writer.write_event(Event::Start(osis))?;
+
+    let mut osis_text = BytesStart::new("osisText");
+    osis_text.push_attribute(("osisIDWork", work));
+    writer.write_event(Event::Start(osis_text))?;
+
+    writer.write_event(Event::Start(BytesStart::new("header")))?;
+    let mut work_element = BytesStart::new("work");
+    work_element.push_attribute(("osisWork", work));
+    writer.write_event(Event::Start(work_element))?;
+    if let Some(name) = chapters.iter().find_map(|c| c.version.as_deref()) {
+        writer.create_element("title").write_text_content(BytesText::new(name))?;
+    }
+    writer.write_event(Event::End(BytesEnd::new("work")))?;
+    writer.write_event(Event::End(BytesEnd::new("header")))?;
+
+    let mut open_book: Option<&str> = None;
+    for chapter in chapters {
+        let book_name = chapter.book.first().map(String::as_str).unwrap_or_default();
+        let book = Book::from_indonesian(book_name)
+            .map(|book| book.osis)
+            .ok_or_else(|| format!("unknown book '{}'", book_name))?;
+
+        if open_book != Some(book) {
+            if open_book.is_some() {
+                writer.write_event(Event::End(BytesEnd::new("div")))?;
+            }
+            let mut div = BytesStart::new("div");
+            div.push_attribute(("type", "book"));
+            div.push_attribute(("osisID", book));
+            writer.write_event(Event::Start(div))?;
+            open_book = Some(book);
+        }
+
+        let chapter_id = format!("{}.{}", book, chapter.chapter);
+        let mut chapter_element = BytesStart::new("chapter");
+        chapter_element.push_attribute(("osisID", chapter_id.as_str()));
+        writer.write_event(Event::Start(chapter_element))?;
+
+        let titles: Vec<(&str, i32)> = chapter.title.iter()
+            .filter_map(|title| parse_title_range(title).map(|(text, start, _)| (text, start)))
+            .collect();
+
+        for verse in &chapter.verses {
+            for (title, _) in titles.iter().filter(|(_, start)| *start == verse.verse) {
+                writer.create_element("title")
+                    .with_attribute(("type", "section"))
+                    .write_text_content(BytesText::new(title))?;
+            }
+            let verse_id = format!("{}.{}", chapter_id, verse.verse);
+            writer.create_element("verse")
+                .with_attribute(("osisID", verse_id.as_str()))
+                .write_text_content(BytesText::new(&verse.content))?;
+        }
+
+        writer.write_event(Event::End(BytesEnd::new("chapter")))?;
+    }
+    if open_book.is_some() {
+        writer.write_event(Event::End(BytesEnd::new("div")))?;
+    }
+
+    writer.write_event(Event::End(BytesEnd::new("osisText")))?;
+    writer.write_event(Event::End(BytesEnd::new("osis")))?;
+    Ok(String::from_utf8(writer.into_inner().into_inner())?)
+}
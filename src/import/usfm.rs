@@ -0,0 +1,218 @@
+use regex::Regex;
+use lazy_static::lazy_static;
+use crate::import::{ChapterBuilder, ImportError, ImportErrorKind};
+use crate::models::bible::BibleChapter;
+use crate::models::book::Book;
+
+lazy_static! {
+    static ref MARKER_REGEX: Regex = Regex::new(r"\\(\+?[A-Za-z]+\d*(?:-[se])?)(\*?)").unwrap();
+}
+
+/// Markers whose content is a note or figure and never part of the verse text
+const NOTE_MARKERS: [&str; 4] = ["f", "fe", "x", "fig"];
+
+/// Inline character markers whose text belongs to the surrounding verse
+const CHARACTER_MARKERS: [&str; 24] = [
+    "w", "add", "nd", "wj", "bk", "qs", "qac", "tl", "k", "sc", "it", "bd", "bdit", "em",
+    "no", "pn", "png", "ord", "sig", "sls", "dc", "rq", "va", "vp",
+];
+
+/// Paragraph and poetry markers: their text continues the current verse
+const PARAGRAPH_MARKERS: [&str; 20] = [
+    "p", "m", "po", "pr", "cls", "pmo", "pm", "pmc", "pmr", "pi", "mi", "nb", "pc", "ph",
+    "b", "q", "qr", "qc", "qm", "li",
+];
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mode {
+    Ignore,
+    BookId,
+    ChapterNumber,
+    VerseNumber,
+    Heading,
+    Content,
+}
+
+struct Parser<'a> {
+    input: &'a str,
+    file: &'a str,
+    version: Option<&'a str>,
+    book: Option<&'static str>,
+    chapter: Option<ChapterBuilder>,
+    verse: Option<(i32, String)>,
+    heading: Option<String>,
+    pending_heading: Option<String>,
+    chapters: Vec<BibleChapter>,
+}
+
+/// Parses one USFM document into chapters.
+///
+/// `\id` codes are mapped to Indonesian book names, `\c` and `\v` become chapters and
+/// verses, and `\s` headings become `title` ranges like the TB scraper produces.
+/// Footnotes, cross references and other non-verse text are dropped.
+pub fn parse_usfm(input: &str, file: &str, version: Option<&str>) -> Result<Vec<BibleChapter>, ImportError> {
+    let mut parser = Parser {
+        input,
+        file,
+        version,
+        book: None,
+        chapter: None,
+        verse: None,
+        heading: None,
+        pending_heading: None,
+        chapters: Vec::new(),
+    };
+
+    let mut mode = Mode::Ignore;
+    let mut note_depth = 0usize;
+    let mut position = 0usize;
+
+    for captures in MARKER_REGEX.captures_iter(input) {
+        let marker = captures.get(0).unwrap();
+        if note_depth == 0 {
+            mode = parser.text(&input[position..marker.start()], mode, position)?;
+        }
+        position = marker.end();
+
+        let name = captures[1].trim_start_matches('+');
+        let closing = !captures[2].is_empty();
+        let base = name.trim_end_matches(|c: char| c.is_ascii_digit());
+
+        if NOTE_MARKERS.contains(&name) {
+            if closing {
+                note_depth = note_depth.saturating_sub(1);
+            } else {
+                note_depth += 1;
+            }
+            continue;
+        }
+        if note_depth > 0 || CHARACTER_MARKERS.contains(&base) {
+            continue;
+        }
+
+        parser.finish_heading();
+        mode = match base {
+            "id" => Mode::BookId,
+            "c" => Mode::ChapterNumber,
+            "v" => Mode::VerseNumber,
+            "s" => {
+                // The heading starts a new section, so the verse before it is complete
+                parser.finish_verse(marker.start())?;
+                parser.heading = Some(String::new());
+                Mode::Heading
+            }
+            _ if PARAGRAPH_MARKERS.contains(&base) => Mode::Content,
+            _ => Mode::Ignore,
+        };
+    }
+
+    if note_depth == 0 {
+        parser.text(&input[position..], mode, position)?;
+    }
+    parser.finish_heading();
+    parser.finish_chapter(input.len())?;
+    Ok(parser.chapters)
+}
+
+impl Parser<'_> {
+    fn text(&mut self, text: &str, mode: Mode, offset: usize) -> Result<Mode, ImportError> {
+        match mode {
+            Mode::Ignore => Ok(Mode::Ignore),
+            Mode::BookId => {
+                let code = text.split_whitespace().next().unwrap_or_default();
+                let book = Book::from_usfm(code)
+                    .map(|book| book.indonesian)
+                    .ok_or_else(|| self.error(offset, ImportErrorKind::UnknownBook(code.to_string())))?;
+                self.finish_chapter(offset)?;
+                self.book = Some(book);
+                Ok(Mode::Ignore)
+            }
+            Mode::ChapterNumber => {
+                let number = text.split_whitespace().next().unwrap_or_default();
+                let chapter = number.parse::<i32>()
+                    .ok()
+                    .filter(|c| *c > 0)
+                    .ok_or_else(|| self.error(offset, ImportErrorKind::InvalidChapter(number.to_string())))?;
+                let book = self.book
+                    .ok_or_else(|| self.error(offset, ImportErrorKind::Malformed("\\c before \\id".to_string())))?;
+                self.finish_chapter(offset)?;
+                let mut builder = ChapterBuilder::new(book, chapter, self.version);
+                if let Some(heading) = self.pending_heading.take() {
+                    builder.heading(&heading);
+                }
+                self.chapter = Some(builder);
+                Ok(Mode::Ignore)
+            }
+            Mode::VerseNumber => {
+                let text = text.trim_start();
+                let (number, content) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+                let digits: String = number.chars().take_while(|c| c.is_ascii_digit()).collect();
+                let verse = digits.parse::<i32>()
+                    .ok()
+                    .filter(|v| *v > 0)
+                    .ok_or_else(|| self.error(offset, ImportErrorKind::InvalidVerse(number.to_string())))?;
+                if self.chapter.is_none() {
+                    return Err(self.error(offset, ImportErrorKind::Malformed("\\v outside of a chapter".to_string())));
+                }
+                self.finish_verse(offset)?;
+                self.verse = Some((verse, strip_attributes(content).to_string()));
+                Ok(Mode::Content)
+            }
+            Mode::Heading => {
+                if let Some(heading) = self.heading.as_mut() {
+                    heading.push_str(strip_attributes(text));
+                }
+                Ok(Mode::Heading)
+            }
+            Mode::Content => {
+                if let Some((_, content)) = self.verse.as_mut() {
+                    content.push_str(strip_attributes(text));
+                }
+                Ok(Mode::Content)
+            }
+        }
+    }
+
+    fn finish_heading(&mut self) {
+        let Some(heading) = self.heading.take() else {
+            return;
+        };
+        match self.chapter.as_mut() {
+            Some(chapter) => chapter.heading(&heading),
+            None => self.pending_heading = Some(heading),
+        }
+    }
+
+    fn finish_verse(&mut self, offset: usize) -> Result<(), ImportError> {
+        if let Some((number, content)) = self.verse.take()
+            && let Some(chapter) = self.chapter.as_mut()
+        {
+            chapter.verse(number, &content).map_err(|kind| self.error(offset, kind))?;
+        }
+        Ok(())
+    }
+
+    fn finish_chapter(&mut self, offset: usize) -> Result<(), ImportError> {
+        self.finish_verse(offset)?;
+        if let Some(mut chapter) = self.chapter.take() {
+            if let Some(heading) = chapter.take_pending_heading() {
+                self.pending_heading = Some(heading);
+            }
+            self.chapters.extend(chapter.finish());
+        }
+        Ok(())
+    }
+
+    fn error(&self, offset: usize, kind: ImportErrorKind) -> ImportError {
+        ImportError {
+            file: self.file.to_string(),
+            line: self.input[..offset.min(self.input.len())].matches('\n').count() + 1,
+            kind,
+        }
+    }
+}
+
+/// Drops `|attribute` lists from character marker content, e.g. `\w kasih|strong="G26"\w*`
+fn strip_attributes(text: &str) -> &str {
+    text.split('|').next().unwrap_or_default()
+}
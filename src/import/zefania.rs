@@ -0,0 +1,195 @@
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use crate::import::{resolve_book_name, xml_attribute, xml_reference, ChapterBuilder, ImportError, ImportErrorKind};
+use crate::models::bible::BibleChapter;
+use crate::models::book::BOOKS;
+
+struct Reading<'a> {
+    input: &'a str,
+    file: &'a str,
+    version: Option<String>,
+    book: Option<&'static str>,
+    chapter: Option<ChapterBuilder>,
+    verse: Option<(i32, String)>,
+    heading: Option<String>,
+    chapters: Vec<BibleChapter>,
+}
+
+/// Parses a Zefania XML bible into chapters.
+///
+/// `BIBLEBOOK`s are identified by their canonical `bnumber`; `bname` and `bsname` are
+/// resolved with [`Book::resolve`](crate::models::book::Book::resolve) only when it is
+/// missing or out of range. `CAPTION`s become `title` ranges and `NOTE`s are dropped.
+/// When `version` is `None` the document's `biblename` is used as the version name.
+pub fn parse_zefania(input: &str, file: &str, version: Option<&str>) -> Result<Vec<BibleChapter>, ImportError> {
+    let mut reading = Reading {
+        input,
+        file,
+        version: version.map(str::to_string),
+        book: None,
+        chapter: None,
+        verse: None,
+        heading: None,
+        chapters: Vec::new(),
+    };
+    let mut reader = Reader::from_str(input);
+    let mut skip_depth = 0usize;
+
+    loop {
+        let position = reader.buffer_position() as usize;
+        let event = reader.read_event()
+            .map_err(|e| reading.error(position, ImportErrorKind::Malformed(e.to_string())))?;
+
+        match event {
+            Event::Eof => break,
+            Event::Start(element) => {
+                if skip_depth > 0 {
+                    skip_depth += 1;
+                    continue;
+                }
+                match element.local_name().as_ref().to_ascii_uppercase().as_slice() {
+                    b"NOTE" | b"REMARK" | b"XREF" | b"INFORMATION" => skip_depth = 1,
+                    b"XMLBIBLE" => reading.bible_start(&element, position)?,
+                    b"BIBLEBOOK" => reading.book_start(&element, position)?,
+                    b"CHAPTER" => reading.chapter_start(&element, position)?,
+                    b"CAPTION" => {
+                        reading.finish_verse(position)?;
+                        reading.heading = Some(String::new());
+                    }
+                    b"VERS" => reading.verse_start(&element, position)?,
+                    _ => {}
+                }
+            }
+            Event::Empty(element) if skip_depth == 0 && element.local_name().as_ref().eq_ignore_ascii_case(b"BR") => {
+                reading.push_text(" ");
+            }
+            Event::End(element) => {
+                if skip_depth > 0 {
+                    skip_depth -= 1;
+                    continue;
+                }
+                match element.local_name().as_ref().to_ascii_uppercase().as_slice() {
+                    b"CAPTION" => reading.finish_heading(),
+                    b"VERS" => reading.finish_verse(position)?,
+                    b"CHAPTER" | b"BIBLEBOOK" => reading.finish_chapter(position)?,
+                    _ => {}
+                }
+            }
+            Event::Text(text) if skip_depth == 0 => {
+                let text = text.decode()
+                    .map_err(|e| reading.error(position, ImportErrorKind::Malformed(e.to_string())))?;
+                reading.push_text(&text);
+            }
+            Event::GeneralRef(reference) if skip_depth == 0 => {
+                let resolved = xml_reference(&reference).map_err(|kind| reading.error(position, kind))?;
+                reading.push_text(&resolved);
+            }
+            _ => {}
+        }
+    }
+
+    reading.finish_chapter(input.len())?;
+    Ok(reading.chapters)
+}
+
+impl Reading<'_> {
+    fn attribute(&self, element: &BytesStart, name: &str, position: usize) -> Result<Option<String>, ImportError> {
+        xml_attribute(element, name)
+            .map(|value| value.map(|value| value.trim().to_string()))
+            .map_err(|kind| self.error(position, kind))
+    }
+
+    fn bible_start(&mut self, element: &BytesStart, position: usize) -> Result<(), ImportError> {
+        if self.version.is_none() {
+            self.version = self.attribute(element, "biblename", position)?.filter(|name| !name.is_empty());
+        }
+        Ok(())
+    }
+
+    fn book_start(&mut self, element: &BytesStart, position: usize) -> Result<(), ImportError> {
+        self.finish_chapter(position)?;
+        let name = self.attribute(element, "bname", position)?;
+        let short_name = self.attribute(element, "bsname", position)?;
+        let number = self.attribute(element, "bnumber", position)?;
+
+        // The number is authoritative; free-text names may be localized or only match by prefix
+        let book = number.as_deref()
+            .and_then(|n| n.parse::<usize>().ok())
+            .and_then(|n| n.checked_sub(1))
+            .and_then(|index| BOOKS.get(index))
+            .map(|book| book.indonesian)
+            .or_else(|| name.as_deref().and_then(resolve_book_name))
+            .or_else(|| short_name.as_deref().and_then(resolve_book_name));
+        let identifier = name.or(short_name).or(number).unwrap_or_default();
+        self.book = Some(book.ok_or_else(|| self.error(position, ImportErrorKind::UnknownBook(identifier)))?);
+        Ok(())
+    }
+
+    fn chapter_start(&mut self, element: &BytesStart, position: usize) -> Result<(), ImportError> {
+        let number = self.attribute(element, "cnumber", position)?.unwrap_or_default();
+        let chapter = number.parse::<i32>()
+            .ok()
+            .filter(|c| *c > 0)
+            .ok_or_else(|| self.error(position, ImportErrorKind::InvalidChapter(number.clone())))?;
+        let book = self.book
+            .ok_or_else(|| self.error(position, ImportErrorKind::Malformed("CHAPTER outside of a BIBLEBOOK".to_string())))?;
+        self.finish_chapter(position)?;
+        self.chapter = Some(ChapterBuilder::new(book, chapter, self.version.as_deref()));
+        Ok(())
+    }
+
+    fn verse_start(&mut self, element: &BytesStart, position: usize) -> Result<(), ImportError> {
+        let number = self.attribute(element, "vnumber", position)?.unwrap_or_default();
+        let verse = number.parse::<i32>()
+            .ok()
+            .filter(|v| *v > 0)
+            .ok_or_else(|| self.error(position, ImportErrorKind::InvalidVerse(number.clone())))?;
+        if self.chapter.is_none() {
+            return Err(self.error(position, ImportErrorKind::Malformed("VERS outside of a CHAPTER".to_string())));
+        }
+        self.finish_verse(position)?;
+        self.verse = Some((verse, String::new()));
+        Ok(())
+    }
+
+    fn push_text(&mut self, text: &str) {
+        if let Some(heading) = self.heading.as_mut() {
+            heading.push_str(text);
+        } else if let Some((_, content)) = self.verse.as_mut() {
+            content.push_str(text);
+        }
+    }
+
+    fn finish_heading(&mut self) {
+        if let Some(heading) = self.heading.take()
+            && let Some(chapter) = self.chapter.as_mut()
+        {
+            chapter.heading(&heading);
+        }
+    }
+
+    fn finish_verse(&mut self, position: usize) -> Result<(), ImportError> {
+        if let Some((number, content)) = self.verse.take()
+            && let Some(chapter) = self.chapter.as_mut()
+        {
+            chapter.verse(number, &content).map_err(|kind| self.error(position, kind))?;
+        }
+        Ok(())
+    }
+
+    fn finish_chapter(&mut self, position: usize) -> Result<(), ImportError> {
+        self.finish_verse(position)?;
+        if let Some(chapter) = self.chapter.take() {
+            self.chapters.extend(chapter.finish());
+        }
+        Ok(())
+    }
+
+    fn error(&self, position: usize, kind: ImportErrorKind) -> ImportError {
+        ImportError {
+            file: self.file.to_string(),
+            line: self.input[..position.min(self.input.len())].matches('\n').count() + 1,
+            kind,
+        }
+    }
+}
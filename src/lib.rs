@@ -1,4 +1,5 @@
 pub mod models;
+pub mod import;
 pub mod services;
 pub mod controllers;
 pub mod routes;
@@ -7,4 +8,6 @@ pub mod config;
 pub mod api_docs;
 
 pub use models::bible::*;
-pub use models::book_translations::*; 
\ No newline at end of file
+pub use models::book::*;
+pub use models::verse_selection::*;
+pub use models::reference::*;
\ No newline at end of file
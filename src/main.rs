@@ -1,15 +1,11 @@
-mod controllers;
 mod middleware;
-mod models;
-mod routes;
-mod error;
-mod services;
-mod config;
 
 use actix_cors::Cors;
 use actix_web::{web, HttpResponse, Responder};
 use middleware::auth::Auth;
 use alkitab_api_rust::api_docs::ApiDoc;
+use alkitab_api_rust::routes;
+use alkitab_api_rust::services::source::DEFAULT_SOURCE;
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 use shuttle_actix_web::ShuttleActixWeb;
@@ -31,7 +27,16 @@ async fn main(
     let auth_secret = secrets.get("SECRET")
         .expect("SECRET not found in secrets store")
         .to_string();
+
+    // The admin endpoints are only mounted when they have a key of their own
+    let admin_secret = secrets.get("ADMIN_SECRET").filter(|secret| !secret.trim().is_empty());
+    if admin_secret.is_none() {
+        log::warn!("ADMIN_SECRET not set, /bible/admin endpoints are disabled");
+    }
     
+    // Build the scripture sources up front so local corpora are loaded before serving traffic
+    lazy_static::initialize(&DEFAULT_SOURCE);
+
     let config = move |cfg: &mut web::ServiceConfig| {
         cfg.service(
                 web::resource("/")
@@ -43,6 +48,16 @@ async fn main(
                     )
                     .route(web::get().to(index))
             )
+            .configure(|cfg| {
+                // Registered before `/bible` so the shared accesskey scope does not claim it
+                if let Some(admin_secret) = &admin_secret {
+                    cfg.service(
+                        web::scope("/bible/admin")
+                            .wrap(Auth::new(admin_secret.clone()).with_header("adminkey"))
+                            .configure(routes::bible::admin_config),
+                    );
+                }
+            })
             .service(
                 web::scope("/bible")
                     .wrap(
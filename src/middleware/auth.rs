@@ -9,11 +9,18 @@ use std::pin::Pin;
 
 pub struct Auth {
     secret: String,
+    header: &'static str,
 }
 
 impl Auth {
     pub fn new(secret: String) -> Self {
-        Self { secret }
+        Self { secret, header: "accesskey" }
+    }
+
+    /// Reads the key from `header` instead of `accesskey`
+    pub fn with_header(mut self, header: &'static str) -> Self {
+        self.header = header;
+        self
     }
 }
 
@@ -33,6 +40,7 @@ where
         ok(AuthMiddleware { 
             service,
             secret: self.secret.clone(),
+            header: self.header,
         })
     }
 }
@@ -40,6 +48,7 @@ where
 pub struct AuthMiddleware<S> {
     service: S,
     secret: String,
+    header: &'static str,
 }
 
 impl<S, B> Service<ServiceRequest> for AuthMiddleware<S>
@@ -56,15 +65,15 @@ where
 
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let token = req.headers()
-            .get("accesskey")
+            .get(self.header)
             .and_then(|h| h.to_str().ok())
             .map(|s| s.trim());
         
         // Check if the token matches the secret
-        if let Some(token_str) = token {
-            if token_str == self.secret {
-                return Box::pin(self.service.call(req));
-            }
+        if let Some(token_str) = token
+            && token_str == self.secret
+        {
+            return Box::pin(self.service.call(req));
         }
 
         // Return an error if authentication fails
@@ -1,19 +1,133 @@
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
+use regex::Regex;
+use lazy_static::lazy_static;
+use std::str::FromStr;
+use crate::error::AppError;
 
-/// Bible book metadata
+/// Number of books in the Old Testament, which come first in the canonical order
+pub const OLD_TESTAMENT_BOOKS: usize = 39;
+
+lazy_static! {
+    static ref TITLE_RANGE_REGEX: Regex = Regex::new(r"^(.*) \((\d+)-(\d+)\)$").unwrap();
+}
+
+/// Bible book metadata, as served by `/bible/find` without `api_version=2`.
+///
+/// Despite their names, `total_verse` and `verses` describe chapters; they are kept that
+/// way for existing clients. [`BookMetadata`] has the real verse counts.
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct BibleMetadata {
     /// Name of the Bible book
     pub book: String,
-    /// Total number of verses in the book
+    /// Number of chapters in the book
     pub total_verse: usize,
-    /// List of verse numbers in the book
+    /// Chapter numbers of the book
     pub verses: Vec<i32>,
 }
 
+impl From<&BookMetadata> for BibleMetadata {
+    fn from(metadata: &BookMetadata) -> Self {
+        BibleMetadata {
+            book: metadata.book.clone(),
+            total_verse: metadata.total_chapters,
+            verses: metadata.chapters.iter().map(|c| c.chapter).collect(),
+        }
+    }
+}
+
+/// Part of the Bible a book belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Testament {
+    Old,
+    New,
+}
+
+impl FromStr for Testament {
+    type Err = AppError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_lowercase().as_str() {
+            "old" => Ok(Testament::Old),
+            "new" => Ok(Testament::New),
+            _ => Err(AppError::InvalidInput(format!("Unknown testament '{}', expected old or new", value))),
+        }
+    }
+}
+
+/// An entry of the books catalogue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BibleBook {
+    /// Indonesian name of the book
+    pub book: String,
+    /// English name of the book
+    pub english_name: String,
+    /// Short name used in alkitab.mobi URLs, e.g. "Kej"
+    pub short_name: String,
+    /// Extra abbreviations accepted for the book, besides its Indonesian name, English name and
+    /// short name; empty for most books
+    pub aliases: Vec<String>,
+    pub testament: Testament,
+    /// Position of the book in the canonical order, starting at 1 for Kejadian
+    pub order: usize,
+    /// Number of chapters in the book
+    pub chapters: i32,
+}
+
+/// A translation the API can serve
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BibleVersion {
+    /// Code to pass as `?version=`, e.g. "tb"
+    pub code: String,
+    /// Full name of the version
+    pub name: String,
+    /// ISO 639-1 language code, when known
+    pub language: Option<String>,
+    /// Testaments the version covers; empty when not known
+    pub testaments: Vec<Testament>,
+    /// Whether chapters come with section titles
+    pub section_titles: bool,
+}
+
+/// Book metadata with chapter and verse counts, as served by `/bible/find?api_version=2`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct BookMetadata {
+    /// Indonesian name of the book
+    pub book: String,
+    /// English name of the book
+    pub english_name: String,
+    pub testament: Testament,
+    /// Position of the book in the canonical order, starting at 1 for Kejadian
+    pub order: usize,
+    /// Versification the verse counts follow, e.g. "tb"
+    pub version: String,
+    /// Number of chapters in the book
+    pub total_chapters: usize,
+    /// Number of verses in the book
+    pub total_verses: usize,
+    /// Every chapter of the book with its verse count
+    pub chapters: Vec<ChapterMetadata>,
+}
+
+/// One chapter of a book in [`BookMetadata`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChapterMetadata {
+    /// Chapter number
+    pub chapter: i32,
+    /// Number of verses in the chapter
+    pub verses: i32,
+    pub testament: Testament,
+    /// Position of the book in the canonical order, starting at 1 for Kejadian
+    pub order: usize,
+    /// Indonesian name of the book
+    pub book: String,
+    /// English name of the book
+    pub english_name: String,
+}
+
 /// A single Bible verse
-#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
 pub struct Verse {
     /// Verse number
     pub verse: i32,
@@ -22,7 +136,7 @@ pub struct Verse {
 }
 
 /// A complete Bible chapter
-#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, ToSchema)]
 pub struct BibleChapter {
     /// Book name(s)
     pub book: Vec<String>,
@@ -38,6 +152,123 @@ pub struct BibleChapter {
     pub verses: Vec<Verse>,
 }
 
+/// A passage resolved from a scripture reference
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct Passage {
+    /// Normalized reference, e.g. "Yohanes 3:16-18"
+    pub reference: String,
+    /// Chapter segments making up the passage, in reading order
+    pub chapters: Vec<BibleChapter>,
+}
+
+/// A chapter read in several versions side by side, aligned by TB verse number
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Comparison {
+    /// Indonesian book name
+    pub book: String,
+    /// Chapter number
+    pub chapter: i32,
+    /// Versions compared, in the order requested
+    pub versions: Vec<String>,
+    /// Every verse found in any version, in order
+    pub verses: Vec<ComparedVerse>,
+    /// Versions that could not be fetched
+    pub errors: Vec<VersionError>,
+}
+
+/// One verse of a [`Comparison`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ComparedVerse {
+    /// Verse number, in TB numbering
+    pub verse: i32,
+    /// Text of the verse in each version, in the order of `versions`; null where a version lacks it
+    pub texts: Vec<Option<String>>,
+}
+
+/// A version that failed in a [`Comparison`]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct VersionError {
+    /// Version code
+    pub version: String,
+    /// Error message
+    pub error: String,
+}
+
+/// Chapter cache counters
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CacheStats {
+    /// Lookups served from the cache
+    pub hits: u64,
+    /// Lookups served from an expired entry while it was refreshed
+    pub stale_hits: u64,
+    /// Lookups that had to go to the scripture source
+    pub misses: u64,
+    /// Chapters currently cached
+    pub entries: usize,
+    /// Maximum number of cached chapters
+    pub max_entries: usize,
+    /// Time a chapter stays cached, in seconds
+    pub ttl_seconds: u64,
+}
+
+/// Progress of a cache prefetch run
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PrefetchReport {
+    /// Version being fetched
+    pub version: String,
+    /// Whether the run is still in progress
+    pub running: bool,
+    /// Number of books walked
+    pub books: usize,
+    /// Number of chapters to fetch
+    pub total_chapters: usize,
+    /// Chapters fetched so far
+    pub fetched: usize,
+    /// Chapters that failed
+    pub failed: usize,
+    /// Details of every failure
+    pub failures: Vec<PrefetchFailure>,
+}
+
+/// A chapter that could not be prefetched
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PrefetchFailure {
+    /// Indonesian book name
+    pub book: String,
+    /// Chapter number
+    pub chapter: i32,
+    /// Error message
+    pub error: String,
+}
+
+/// State of the circuit breaker guarding the upstream
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests go to the upstream
+    Closed,
+    /// Requests fail fast until the cool-down has passed
+    Open,
+    /// The cool-down has passed and the next request probes the upstream
+    HalfOpen,
+}
+
+/// Health of the upstream as seen by the circuit breaker
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CircuitBreakerStatus {
+    pub state: CircuitState,
+    /// Upstream failures since the last success
+    pub consecutive_failures: u32,
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+    /// Time the circuit stays open before probing, in seconds
+    pub cool_down_seconds: u64,
+    /// Seconds until the next probe, while the circuit is open
+    pub retry_after_seconds: Option<u64>,
+    /// Most recent upstream error, cleared on success
+    pub last_error: Option<String>,
+}
+
 /// Search result
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct SearchResult {
@@ -52,4 +283,13 @@ pub struct SearchResult {
 pub struct ErrorResponse {
     /// Error message
     pub error: String,
+}
+
+/// Splits a section title such as "Kasih Allah (14-21)" into its text and verse range
+pub fn parse_title_range(title: &str) -> Option<(&str, i32, i32)> {
+    let captures = TITLE_RANGE_REGEX.captures(title)?;
+    let text = captures.get(1)?.as_str();
+    let start = captures[2].parse().ok()?;
+    let end = captures[3].parse().ok()?;
+    Some((text, start, end))
 }
\ No newline at end of file
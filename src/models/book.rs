@@ -0,0 +1,196 @@
+use std::fmt;
+use crate::models::bible::{Testament, OLD_TESTAMENT_BOOKS};
+use crate::models::versification;
+
+/// One of the 66 books, with every name and code it goes by.
+///
+/// [`BOOKS`] is the only place book names are listed: book names in requests, references,
+/// corpus files and alkitab.mobi URLs are all resolved through it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Book {
+    /// Indonesian name as used by the TB, e.g. "Kejadian"; the name used throughout the API
+    pub indonesian: &'static str,
+    /// English name, e.g. "Genesis"
+    pub english: &'static str,
+    /// Segment of alkitab.mobi chapter URLs, e.g. "Kej" in `/tb/Kej/1`; also the short name
+    pub path_segment: &'static str,
+    /// USFM/Paratext book code, e.g. "GEN"
+    pub usfm: &'static str,
+    /// OSIS book identifier, e.g. "Gen"
+    pub osis: &'static str,
+    /// Other abbreviations accepted for the book
+    pub aliases: &'static [&'static str],
+}
+
+/// Every book in canonical order, Kejadian through Wahyu
+pub static BOOKS: [Book; 66] = [
+    Book { indonesian: "Kejadian", english: "Genesis", path_segment: "Kej", usfm: "GEN", osis: "Gen", aliases: &[] },
+    Book { indonesian: "Keluaran", english: "Exodus", path_segment: "Kel", usfm: "EXO", osis: "Exod", aliases: &[] },
+    Book { indonesian: "Imamat", english: "Leviticus", path_segment: "Im", usfm: "LEV", osis: "Lev", aliases: &[] },
+    Book { indonesian: "Bilangan", english: "Numbers", path_segment: "Bil", usfm: "NUM", osis: "Num", aliases: &[] },
+    Book { indonesian: "Ulangan", english: "Deuteronomy", path_segment: "Ula", usfm: "DEU", osis: "Deut", aliases: &[] },
+    Book { indonesian: "Yosua", english: "Joshua", path_segment: "Yos", usfm: "JOS", osis: "Josh", aliases: &[] },
+    Book { indonesian: "Hakim-hakim", english: "Judges", path_segment: "Hak", usfm: "JDG", osis: "Judg", aliases: &[] },
+    Book { indonesian: "Rut", english: "Ruth", path_segment: "Rut", usfm: "RUT", osis: "Ruth", aliases: &[] },
+    Book { indonesian: "1 Samuel", english: "1 Samuel", path_segment: "1Sa", usfm: "1SA", osis: "1Sam", aliases: &[] },
+    Book { indonesian: "2 Samuel", english: "2 Samuel", path_segment: "2Sa", usfm: "2SA", osis: "2Sam", aliases: &[] },
+    Book { indonesian: "1 Raja-raja", english: "1 Kings", path_segment: "1Ra", usfm: "1KI", osis: "1Kgs", aliases: &[] },
+    Book { indonesian: "2 Raja-raja", english: "2 Kings", path_segment: "2Ra", usfm: "2KI", osis: "2Kgs", aliases: &[] },
+    Book { indonesian: "1 Tawarikh", english: "1 Chronicles", path_segment: "1Ta", usfm: "1CH", osis: "1Chr", aliases: &[] },
+    Book { indonesian: "2 Tawarikh", english: "2 Chronicles", path_segment: "2Ta", usfm: "2CH", osis: "2Chr", aliases: &[] },
+    Book { indonesian: "Ezra", english: "Ezra", path_segment: "Eza", usfm: "EZR", osis: "Ezra", aliases: &["ezr"] },
+    Book { indonesian: "Nehemia", english: "Nehemiah", path_segment: "Neh", usfm: "NEH", osis: "Neh", aliases: &[] },
+    Book { indonesian: "Ester", english: "Esther", path_segment: "Est", usfm: "EST", osis: "Esth", aliases: &[] },
+    Book { indonesian: "Ayub", english: "Job", path_segment: "Ayu", usfm: "JOB", osis: "Job", aliases: &["ayb"] },
+    Book { indonesian: "Mazmur", english: "Psalms", path_segment: "Maz", usfm: "PSA", osis: "Ps", aliases: &["mzm"] },
+    Book { indonesian: "Amsal", english: "Proverbs", path_segment: "Ams", usfm: "PRO", osis: "Prov", aliases: &[] },
+    Book { indonesian: "Pengkhotbah", english: "Ecclesiastes", path_segment: "Pkh", usfm: "ECC", osis: "Eccl", aliases: &[] },
+    Book { indonesian: "Kidung Agung", english: "Song of Solomon", path_segment: "Kid", usfm: "SNG", osis: "Song", aliases: &[] },
+    Book { indonesian: "Yesaya", english: "Isaiah", path_segment: "Yes", usfm: "ISA", osis: "Isa", aliases: &[] },
+    Book { indonesian: "Yeremia", english: "Jeremiah", path_segment: "Yer", usfm: "JER", osis: "Jer", aliases: &[] },
+    Book { indonesian: "Ratapan", english: "Lamentations", path_segment: "Rat", usfm: "LAM", osis: "Lam", aliases: &[] },
+    Book { indonesian: "Yehezkiel", english: "Ezekiel", path_segment: "Yeh", usfm: "EZK", osis: "Ezek", aliases: &[] },
+    Book { indonesian: "Daniel", english: "Daniel", path_segment: "Dan", usfm: "DAN", osis: "Dan", aliases: &[] },
+    Book { indonesian: "Hosea", english: "Hosea", path_segment: "Hos", usfm: "HOS", osis: "Hos", aliases: &[] },
+    Book { indonesian: "Yoel", english: "Joel", path_segment: "Yoe", usfm: "JOL", osis: "Joel", aliases: &[] },
+    Book { indonesian: "Amos", english: "Amos", path_segment: "Amo", usfm: "AMO", osis: "Amos", aliases: &[] },
+    Book { indonesian: "Obaja", english: "Obadiah", path_segment: "Oba", usfm: "OBA", osis: "Obad", aliases: &[] },
+    Book { indonesian: "Yunus", english: "Jonah", path_segment: "Yun", usfm: "JON", osis: "Jonah", aliases: &[] },
+    Book { indonesian: "Mikha", english: "Micah", path_segment: "Mik", usfm: "MIC", osis: "Mic", aliases: &[] },
+    Book { indonesian: "Nahum", english: "Nahum", path_segment: "Nah", usfm: "NAM", osis: "Nah", aliases: &[] },
+    Book { indonesian: "Habakuk", english: "Habakkuk", path_segment: "Hab", usfm: "HAB", osis: "Hab", aliases: &[] },
+    Book { indonesian: "Zefanya", english: "Zephaniah", path_segment: "Zef", usfm: "ZEP", osis: "Zeph", aliases: &[] },
+    Book { indonesian: "Hagai", english: "Haggai", path_segment: "Hag", usfm: "HAG", osis: "Hag", aliases: &[] },
+    Book { indonesian: "Zakharia", english: "Zechariah", path_segment: "Zak", usfm: "ZEC", osis: "Zech", aliases: &[] },
+    Book { indonesian: "Maleakhi", english: "Malachi", path_segment: "Mal", usfm: "MAL", osis: "Mal", aliases: &[] },
+    Book { indonesian: "Matius", english: "Matthew", path_segment: "Mat", usfm: "MAT", osis: "Matt", aliases: &[] },
+    Book { indonesian: "Markus", english: "Mark", path_segment: "Mar", usfm: "MRK", osis: "Mark", aliases: &[] },
+    Book { indonesian: "Lukas", english: "Luke", path_segment: "Luk", usfm: "LUK", osis: "Luke", aliases: &[] },
+    Book { indonesian: "Yohanes", english: "John", path_segment: "Yoh", usfm: "JHN", osis: "John", aliases: &[] },
+    Book { indonesian: "Kisah Para Rasul", english: "Acts", path_segment: "Kis", usfm: "ACT", osis: "Acts", aliases: &[] },
+    Book { indonesian: "Roma", english: "Romans", path_segment: "Rom", usfm: "ROM", osis: "Rom", aliases: &[] },
+    Book { indonesian: "1 Korintus", english: "1 Corinthians", path_segment: "1Ko", usfm: "1CO", osis: "1Cor", aliases: &[] },
+    Book { indonesian: "2 Korintus", english: "2 Corinthians", path_segment: "2Ko", usfm: "2CO", osis: "2Cor", aliases: &[] },
+    Book { indonesian: "Galatia", english: "Galatians", path_segment: "Gal", usfm: "GAL", osis: "Gal", aliases: &[] },
+    Book { indonesian: "Efesus", english: "Ephesians", path_segment: "Efe", usfm: "EPH", osis: "Eph", aliases: &[] },
+    Book { indonesian: "Filipi", english: "Philippians", path_segment: "Fip", usfm: "PHP", osis: "Phil", aliases: &[] },
+    Book { indonesian: "Kolose", english: "Colossians", path_segment: "Kol", usfm: "COL", osis: "Col", aliases: &[] },
+    Book { indonesian: "1 Tesalonika", english: "1 Thessalonians", path_segment: "1Te", usfm: "1TH", osis: "1Thess", aliases: &[] },
+    Book { indonesian: "2 Tesalonika", english: "2 Thessalonians", path_segment: "2Te", usfm: "2TH", osis: "2Thess", aliases: &[] },
+    Book { indonesian: "1 Timotius", english: "1 Timothy", path_segment: "1Ti", usfm: "1TI", osis: "1Tim", aliases: &[] },
+    Book { indonesian: "2 Timotius", english: "2 Timothy", path_segment: "2Ti", usfm: "2TI", osis: "2Tim", aliases: &[] },
+    Book { indonesian: "Titus", english: "Titus", path_segment: "Tit", usfm: "TIT", osis: "Titus", aliases: &[] },
+    Book { indonesian: "Filemon", english: "Philemon", path_segment: "Fim", usfm: "PHM", osis: "Phlm", aliases: &[] },
+    Book { indonesian: "Ibrani", english: "Hebrews", path_segment: "Ibr", usfm: "HEB", osis: "Heb", aliases: &[] },
+    Book { indonesian: "Yakobus", english: "James", path_segment: "Yak", usfm: "JAS", osis: "Jas", aliases: &[] },
+    Book { indonesian: "1 Petrus", english: "1 Peter", path_segment: "1Pe", usfm: "1PE", osis: "1Pet", aliases: &[] },
+    Book { indonesian: "2 Petrus", english: "2 Peter", path_segment: "2Pe", usfm: "2PE", osis: "2Pet", aliases: &[] },
+    Book { indonesian: "1 Yohanes", english: "1 John", path_segment: "1Yo", usfm: "1JN", osis: "1John", aliases: &[] },
+    Book { indonesian: "2 Yohanes", english: "2 John", path_segment: "2Yo", usfm: "2JN", osis: "2John", aliases: &[] },
+    Book { indonesian: "3 Yohanes", english: "3 John", path_segment: "3Yo", usfm: "3JN", osis: "3John", aliases: &[] },
+    Book { indonesian: "Yudas", english: "Jude", path_segment: "Yud", usfm: "JUD", osis: "Jude", aliases: &[] },
+    Book { indonesian: "Wahyu", english: "Revelation", path_segment: "Wah", usfm: "REV", osis: "Rev", aliases: &["why"] },
+];
+
+/// Why a book name could not be resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BookError {
+    /// The name was empty
+    Missing,
+    /// No book goes by the name
+    Unknown(String),
+    /// The name is the start of several books' names, listed by Indonesian name
+    Ambiguous(String, Vec<&'static str>),
+}
+
+impl fmt::Display for BookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BookError::Missing => write!(f, "missing book name"),
+            BookError::Unknown(name) => write!(f, "unknown book '{}'", name),
+            BookError::Ambiguous(name, candidates) => write!(f, "ambiguous book '{}' (could be {})", name, candidates.join(", ")),
+        }
+    }
+}
+
+impl Book {
+    /// Resolves an Indonesian or English name, short name or alias, e.g. "Kej", "1 Kor",
+    /// "Gen" or "Mazmur". Case, spaces and a trailing dot are ignored, and the start of a
+    /// name is enough as long as only one book's name starts that way.
+    pub fn resolve(name: &str) -> Result<&'static Book, BookError> {
+        let normalized = normalize(name);
+        if normalized.is_empty() {
+            return Err(BookError::Missing);
+        }
+        if let Some(book) = Self::lookup(name) {
+            return Ok(book);
+        }
+
+        let candidates: Vec<&'static Book> = BOOKS.iter()
+            .filter(|book| [book.indonesian, book.english].iter().any(|n| normalize(n).starts_with(&normalized)))
+            .collect();
+        match candidates.as_slice() {
+            [book] => Ok(book),
+            [] => Err(BookError::Unknown(name.trim().to_string())),
+            _ => {
+                let mut names: Vec<&'static str> = candidates.iter().map(|book| book.indonesian).collect();
+                names.sort_unstable();
+                Err(BookError::Ambiguous(name.trim().to_string(), names))
+            }
+        }
+    }
+
+    /// Book whose name, short name or alias is exactly `name`, ignoring case, spaces and a trailing dot
+    pub fn lookup(name: &str) -> Option<&'static Book> {
+        let normalized = normalize(name);
+        BOOKS.iter().find(|book| book.names().any(|n| normalize(n) == normalized))
+    }
+
+    /// Book by its Indonesian name, ignoring case
+    pub fn from_indonesian(name: &str) -> Option<&'static Book> {
+        BOOKS.iter().find(|book| book.indonesian.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Book for a USFM book code such as "GEN" or "1CO"
+    pub fn from_usfm(code: &str) -> Option<&'static Book> {
+        BOOKS.iter().find(|book| book.usfm.eq_ignore_ascii_case(code.trim()))
+    }
+
+    /// Book for an OSIS book identifier such as "Gen" or "1Cor"
+    pub fn from_osis(id: &str) -> Option<&'static Book> {
+        BOOKS.iter().find(|book| book.osis.eq_ignore_ascii_case(id.trim()))
+    }
+
+    /// Position in the canonical order, starting at 0 for Kejadian
+    pub fn index(&self) -> usize {
+        BOOKS.iter().position(|book| book.indonesian == self.indonesian).unwrap_or_default()
+    }
+
+    pub fn testament(&self) -> Testament {
+        if self.index() < OLD_TESTAMENT_BOOKS { Testament::Old } else { Testament::New }
+    }
+
+    /// Number of chapters, from the versification table
+    pub fn chapter_count(&self) -> i32 {
+        versification::chapter_count(self.indonesian).unwrap_or_default()
+    }
+
+    /// Every name the book can be looked up by
+    fn names(&self) -> impl Iterator<Item = &'static str> {
+        [self.indonesian, self.english, self.path_segment].into_iter().chain(self.aliases.iter().copied())
+    }
+}
+
+/// Position of a book in the canonical order by Indonesian name, starting at 0 for Kejadian
+pub fn book_order(indonesian: &str) -> Option<usize> {
+    Book::from_indonesian(indonesian).map(Book::index)
+}
+
+fn normalize(name: &str) -> String {
+    name.trim()
+        .trim_end_matches('.')
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
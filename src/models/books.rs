@@ -0,0 +1,25 @@
+use crate::models::bible::{BibleBook, Testament};
+use crate::models::book::{Book, BOOKS};
+
+/// Every book in canonical order, optionally only those of one testament
+pub fn catalogue(testament: Option<Testament>) -> Vec<BibleBook> {
+    BOOKS.iter()
+        .map(describe_book)
+        .filter(|book| testament.is_none_or(|testament| book.testament == testament))
+        .collect()
+}
+
+fn describe_book(book: &'static Book) -> BibleBook {
+    let mut aliases: Vec<String> = book.aliases.iter().map(|alias| alias.to_string()).collect();
+    aliases.sort();
+
+    BibleBook {
+        book: book.indonesian.to_string(),
+        english_name: book.english.to_string(),
+        short_name: book.path_segment.to_string(),
+        aliases,
+        testament: book.testament(),
+        order: book.index() + 1,
+        chapters: book.chapter_count(),
+    }
+}
@@ -1,2 +1,7 @@
 pub mod bible;
-pub mod book_translations;
\ No newline at end of file
+pub mod books;
+pub mod book;
+pub mod verse_selection;
+pub mod reference;
+pub mod versification;
+pub mod versions;
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::fmt;
+use regex::Regex;
+use lazy_static::lazy_static;
+use crate::error::AppError;
+use crate::models::book::{book_order, Book, BOOKS};
+use crate::models::verse_selection::VerseSelection;
+
+/// Most chapters a single reference, or all references of one request together, may span
+pub const MAX_PASSAGE_CHAPTERS: usize = 50;
+
+lazy_static! {
+    static ref CHAPTER_REGEX: Regex = Regex::new(
+        r"^(?:(?P<book>(?:[1-3]\s*)?\p{L}[^\d]*?)\s*)?(?P<chapter>\d+)(?:\s*[:.]\s*(?P<verses>\d[\d\s,\-]*))?$"
+    ).unwrap();
+    static ref SPAN_REGEX: Regex = Regex::new(r"^(?P<start>.*?\d)\s*-\s*(?P<end>\S.*)$").unwrap();
+    static ref POINT_REGEX: Regex = Regex::new(
+        r"^(?:(?P<book>(?:[1-3]\s*)?\p{L}[^\d]*?)\s*)?(?P<chapter>\d+)(?:\s*[:.]\s*(?P<verse>\d+))?$"
+    ).unwrap();
+}
+
+/// A position in the Bible. `verse` is `None` when the whole chapter is meant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VersePoint {
+    /// Indonesian book name, e.g. "Kejadian"
+    pub book: &'static str,
+    /// Chapter number
+    pub chapter: i32,
+    /// Verse number, if any
+    pub verse: Option<i32>,
+}
+
+/// A parsed scripture reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptureReference {
+    /// A whole chapter or selected verses of one chapter, e.g. "Yoh 3:16-18"
+    Chapter {
+        book: &'static str,
+        chapter: i32,
+        verses: Option<VerseSelection>,
+    },
+    /// A continuous passage crossing chapter or book boundaries, e.g. "Kej 1:26-2:3"
+    Span {
+        start: VersePoint,
+        end: VersePoint,
+    },
+}
+
+/// The verses of a single chapter covered by a reference
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChapterSegment {
+    pub book: &'static str,
+    pub chapter: i32,
+    pub verses: SegmentVerses,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SegmentVerses {
+    /// Every verse in the chapter
+    All,
+    /// An explicit verse selection
+    Selection(VerseSelection),
+    /// From the given verse to the end of the chapter
+    From(i32),
+}
+
+impl ScriptureReference {
+    /// Books whose chapter count must be known to expand this reference into chapters
+    pub fn books_needing_chapter_counts(&self) -> Vec<&'static str> {
+        match self {
+            ScriptureReference::Chapter { .. } => Vec::new(),
+            ScriptureReference::Span { start, end } => {
+                let (Some(first), Some(last)) = (book_order(start.book), book_order(end.book)) else {
+                    return Vec::new();
+                };
+                BOOKS[first..last].iter().map(|book| book.indonesian).collect()
+            }
+        }
+    }
+
+    /// Expands the reference into per-chapter segments in reading order.
+    ///
+    /// `chapter_counts` must hold the number of chapters of every book returned by
+    /// [`ScriptureReference::books_needing_chapter_counts`].
+    pub fn segments(&self, chapter_counts: &HashMap<&'static str, i32>) -> Result<Vec<ChapterSegment>, AppError> {
+        let (start, end) = match self {
+            ScriptureReference::Chapter { book, chapter, verses } => {
+                return Ok(vec![ChapterSegment {
+                    book,
+                    chapter: *chapter,
+                    verses: verses.clone().map_or(SegmentVerses::All, SegmentVerses::Selection),
+                }]);
+            }
+            ScriptureReference::Span { start, end } => (start, end),
+        };
+
+        let first_book = book_order(start.book)
+            .ok_or_else(|| AppError::InvalidBookError(start.book.to_string()))?;
+        let last_book = book_order(end.book)
+            .ok_or_else(|| AppError::InvalidBookError(end.book.to_string()))?;
+
+        let mut segments = Vec::new();
+        for (index, book) in BOOKS.iter().enumerate().take(last_book + 1).skip(first_book) {
+            let book = book.indonesian;
+            let first_chapter = if index == first_book { start.chapter } else { 1 };
+            let last_chapter = if index == last_book {
+                end.chapter
+            } else {
+                *chapter_counts.get(&book)
+                    .ok_or_else(|| AppError::NotFound(format!("Chapter count unknown for {}", book)))?
+            };
+
+            if first_chapter > last_chapter {
+                return Err(AppError::NotFound(format!(
+                    "{} has only {} chapters", book, last_chapter
+                )));
+            }
+
+            for chapter in first_chapter..=last_chapter {
+                let from = if index == first_book && chapter == start.chapter { start.verse } else { None };
+                let to = if index == last_book && chapter == end.chapter { end.verse } else { None };
+                let verses = match (from, to) {
+                    (None, None) => SegmentVerses::All,
+                    (Some(from), None) => SegmentVerses::From(from),
+                    (None, Some(to)) => SegmentVerses::Selection(VerseSelection::range(1, to)),
+                    (Some(from), Some(to)) => SegmentVerses::Selection(VerseSelection::range(from, to)),
+                };
+                segments.push(ChapterSegment { book, chapter, verses });
+
+                if segments.len() > MAX_PASSAGE_CHAPTERS {
+                    return Err(AppError::InvalidInput(format!(
+                        "Passage '{}' spans more than {} chapters", self, MAX_PASSAGE_CHAPTERS
+                    )));
+                }
+            }
+        }
+
+        Ok(segments)
+    }
+}
+
+impl fmt::Display for VersePoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.book, location(self.chapter, self.verse))
+    }
+}
+
+impl fmt::Display for ScriptureReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScriptureReference::Chapter { book, chapter, verses: Some(verses) } => {
+                write!(f, "{} {}:{}", book, chapter, verses)
+            }
+            ScriptureReference::Chapter { book, chapter, verses: None } => write!(f, "{} {}", book, chapter),
+            ScriptureReference::Span { start, end } if start.book == end.book => {
+                write!(f, "{}-{}", start, location(end.chapter, end.verse))
+            }
+            ScriptureReference::Span { start, end } => write!(f, "{} - {}", start, end),
+        }
+    }
+}
+
+fn location(chapter: i32, verse: Option<i32>) -> String {
+    match verse {
+        Some(verse) => format!("{}:{}", chapter, verse),
+        None => chapter.to_string(),
+    }
+}
+
+/// Parses free-text references such as "Yoh 3:16-18; Mzm 23", "Gen 1:1–3",
+/// "Kej 1:26-2:3" or "Mal 4:5 - Mat 1:1".
+///
+/// Segments are separated by `;`. A segment without a book name continues the
+/// book of the previous segment, so "Yoh 3:16; 4:1" reads as two references to Yohanes.
+pub fn parse_references(input: &str) -> Result<Vec<ScriptureReference>, AppError> {
+    let mut references = Vec::new();
+    let mut previous_book: Option<&'static str> = None;
+
+    for segment in input.split(';') {
+        let segment = segment.trim();
+        if segment.is_empty() {
+            continue;
+        }
+
+        let reference = parse_segment(segment, previous_book)?;
+        previous_book = Some(match &reference {
+            ScriptureReference::Chapter { book, .. } => book,
+            ScriptureReference::Span { end, .. } => end.book,
+        });
+        references.push(reference);
+    }
+
+    if references.is_empty() {
+        return Err(AppError::InvalidInput("Empty scripture reference".to_string()));
+    }
+
+    Ok(references)
+}
+
+fn parse_segment(segment: &str, previous_book: Option<&'static str>) -> Result<ScriptureReference, AppError> {
+    let normalized = segment.replace(['–', '—'], "-");
+
+    if let Some(captures) = CHAPTER_REGEX.captures(&normalized) {
+        let book = parse_book(captures.name("book").map(|m| m.as_str()), previous_book, segment)?;
+        let chapter = parse_number(&captures["chapter"], segment, "chapter")?;
+        let verses = match captures.name("verses") {
+            Some(verses) => Some(VerseSelection::parse(verses.as_str())
+                .map_err(|e| segment_error(segment, &error_message(e)))?),
+            None => None,
+        };
+        return Ok(ScriptureReference::Chapter { book, chapter, verses });
+    }
+
+    let captures = SPAN_REGEX.captures(&normalized)
+        .ok_or_else(|| segment_error(segment, "expected '<book> <chapter>[:<verses>]'"))?;
+    let start = parse_point(&captures["start"], previous_book, segment)?;
+    let end = parse_point(&captures["end"], Some(start.book), segment)?;
+
+    let start_key = (book_order(start.book), start.chapter, start.verse.unwrap_or(0));
+    let end_key = (book_order(end.book), end.chapter, end.verse.unwrap_or(i32::MAX));
+    if end_key < start_key {
+        return Err(segment_error(segment, "passage ends before it starts"));
+    }
+
+    Ok(ScriptureReference::Span { start, end })
+}
+
+fn parse_point(text: &str, default_book: Option<&'static str>, segment: &str) -> Result<VersePoint, AppError> {
+    let captures = POINT_REGEX.captures(text.trim())
+        .ok_or_else(|| segment_error(segment, &format!("invalid passage bound '{}'", text.trim())))?;
+    let book = parse_book(captures.name("book").map(|m| m.as_str()), default_book, segment)?;
+    let chapter = parse_number(&captures["chapter"], segment, "chapter")?;
+    let verse = match captures.name("verse") {
+        Some(verse) => Some(parse_number(verse.as_str(), segment, "verse")?),
+        None => None,
+    };
+    Ok(VersePoint { book, chapter, verse })
+}
+
+fn parse_book(name: Option<&str>, default_book: Option<&'static str>, segment: &str) -> Result<&'static str, AppError> {
+    match name {
+        Some(name) => Book::resolve(name)
+            .map(|book| book.indonesian)
+            .map_err(|error| segment_error(segment, &error.to_string())),
+        None => default_book.ok_or_else(|| segment_error(segment, "missing book name")),
+    }
+}
+
+fn parse_number(value: &str, segment: &str, what: &str) -> Result<i32, AppError> {
+    value.parse::<i32>()
+        .ok()
+        .filter(|n| *n > 0)
+        .ok_or_else(|| segment_error(segment, &format!("invalid {} number", what)))
+}
+
+fn segment_error(segment: &str, reason: &str) -> AppError {
+    AppError::InvalidInput(format!("Could not parse reference '{}': {}", segment, reason))
+}
+
+fn error_message(error: AppError) -> String {
+    match error {
+        AppError::InvalidInput(msg) => msg,
+        other => other.to_string(),
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt;
+use crate::error::AppError;
+
+/// An inclusive range of verse numbers within a chapter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VerseRange {
+    /// First verse of the range
+    pub start: i32,
+    /// Last verse of the range (inclusive)
+    pub end: i32,
+}
+
+/// A selection of verses within a single chapter, e.g. `16`, `16-18` or `1-3,7,10-12`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerseSelection {
+    ranges: Vec<VerseRange>,
+}
+
+impl VerseSelection {
+    /// Parses a verse list such as `1-3,7,10-12`. En and em dashes are accepted as range separators.
+    pub fn parse(input: &str) -> Result<Self, AppError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(AppError::InvalidInput("Empty verse selection".to_string()));
+        }
+
+        let mut ranges = Vec::new();
+        for part in input.split(',') {
+            let part = part.trim();
+            let bounds: Vec<&str> = part.split(['-', '–', '—']).map(str::trim).collect();
+            let range = match bounds.as_slice() {
+                [single] => {
+                    let verse = parse_verse_number(single, input)?;
+                    VerseRange { start: verse, end: verse }
+                }
+                [start, end] => VerseRange {
+                    start: parse_verse_number(start, input)?,
+                    end: parse_verse_number(end, input)?,
+                },
+                _ => {
+                    return Err(AppError::InvalidInput(format!("Invalid verse range '{}' in '{}'", part, input)));
+                }
+            };
+
+            if range.end < range.start {
+                return Err(AppError::InvalidInput(format!("Verse range '{}' ends before it starts", part)));
+            }
+            ranges.push(range);
+        }
+
+        Ok(VerseSelection { ranges })
+    }
+
+    /// A single inclusive range of verses
+    pub fn range(start: i32, end: i32) -> Self {
+        VerseSelection { ranges: vec![VerseRange { start, end }] }
+    }
+
+    /// Builds a selection from individual verse numbers, merging consecutive verses into ranges
+    pub fn from_verses(verses: &[i32]) -> Self {
+        let mut sorted = verses.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        let mut ranges: Vec<VerseRange> = Vec::new();
+        for verse in sorted {
+            match ranges.last_mut() {
+                Some(last) if last.end + 1 == verse => last.end = verse,
+                _ => ranges.push(VerseRange { start: verse, end: verse }),
+            }
+        }
+        VerseSelection { ranges }
+    }
+
+    pub fn ranges(&self) -> &[VerseRange] {
+        &self.ranges
+    }
+
+    pub fn contains(&self, verse: i32) -> bool {
+        self.ranges.iter().any(|r| r.start <= verse && verse <= r.end)
+    }
+
+    /// Every verse number covered by the selection, in the order it was written
+    pub fn verses(&self) -> impl Iterator<Item = i32> + '_ {
+        self.ranges.iter().flat_map(|r| r.start..=r.end)
+    }
+}
+
+impl fmt::Display for VerseSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<String> = self.ranges.iter()
+            .map(|r| if r.start == r.end {
+                r.start.to_string()
+            } else {
+                format!("{}-{}", r.start, r.end)
+            })
+            .collect();
+        write!(f, "{}", parts.join(","))
+    }
+}
+
+fn parse_verse_number(value: &str, input: &str) -> Result<i32, AppError> {
+    match value.parse::<i32>() {
+        Ok(verse) if verse > 0 => Ok(verse),
+        _ => Err(AppError::InvalidInput(format!("Invalid verse number '{}' in '{}'", value, input))),
+    }
+}
@@ -0,0 +1,379 @@
+use crate::models::book::book_order;
+use crate::models::verse_selection::VerseSelection;
+
+/// Verses per chapter in the English (KJV) versification, one slice per book in the
+/// order of `BOOKS`. 1,189 chapters and 31,102 verses in total.
+const KJV_VERSE_COUNTS: [&[i32]; 66] = [
+    // Kejadian
+    &[
+        31, 25, 24, 26, 32, 22, 24, 22, 29, 32, 32, 20, 18, 24, 21, 16, 27, 33, 38, 18,
+        34, 24, 20, 67, 34, 35, 46, 22, 35, 43, 55, 32, 20, 31, 29, 43, 36, 30, 23, 23,
+        57, 38, 34, 34, 28, 34, 31, 22, 33, 26,
+    ],
+    // Keluaran
+    &[
+        22, 25, 22, 31, 23, 30, 25, 32, 35, 29, 10, 51, 22, 31, 27, 36, 16, 27, 25, 26,
+        36, 31, 33, 18, 40, 37, 21, 43, 46, 38, 18, 35, 23, 35, 35, 38, 29, 31, 43, 38,
+    ],
+    // Imamat
+    &[
+        17, 16, 17, 35, 19, 30, 38, 36, 24, 20, 47, 8, 59, 57, 33, 34, 16, 30, 37, 27,
+        24, 33, 44, 23, 55, 46, 34,
+    ],
+    // Bilangan
+    &[
+        54, 34, 51, 49, 31, 27, 89, 26, 23, 36, 35, 16, 33, 45, 41, 50, 13, 32, 22, 29,
+        35, 41, 30, 25, 18, 65, 23, 31, 40, 16, 54, 42, 56, 29, 34, 13,
+    ],
+    // Ulangan
+    &[
+        46, 37, 29, 49, 33, 25, 26, 20, 29, 22, 32, 32, 18, 29, 23, 22, 20, 22, 21, 20,
+        23, 30, 25, 22, 19, 19, 26, 68, 29, 20, 30, 52, 29, 12,
+    ],
+    // Yosua
+    &[
+        18, 24, 17, 24, 15, 27, 26, 35, 27, 43, 23, 24, 33, 15, 63, 10, 18, 28, 51, 9,
+        45, 34, 16, 33,
+    ],
+    // Hakim-hakim
+    &[
+        36, 23, 31, 24, 31, 40, 25, 35, 57, 18, 40, 15, 25, 20, 20, 31, 13, 31, 30, 48,
+        25,
+    ],
+    // Rut
+    &[22, 23, 18, 22],
+    // 1 Samuel
+    &[
+        28, 36, 21, 22, 12, 21, 17, 22, 27, 27, 15, 25, 23, 52, 35, 23, 58, 30, 24, 42,
+        15, 23, 29, 22, 44, 25, 12, 25, 11, 31, 13,
+    ],
+    // 2 Samuel
+    &[
+        27, 32, 39, 12, 25, 23, 29, 18, 13, 19, 27, 31, 39, 33, 37, 23, 29, 33, 43, 26,
+        22, 51, 39, 25,
+    ],
+    // 1 Raja-raja
+    &[
+        53, 46, 28, 34, 18, 38, 51, 66, 28, 29, 43, 33, 34, 31, 34, 34, 24, 46, 21, 43,
+        29, 53,
+    ],
+    // 2 Raja-raja
+    &[
+        18, 25, 27, 44, 27, 33, 20, 29, 37, 36, 21, 21, 25, 29, 38, 20, 41, 37, 37, 21,
+        26, 20, 37, 20, 30,
+    ],
+    // 1 Tawarikh
+    &[
+        54, 55, 24, 43, 26, 81, 40, 40, 44, 14, 47, 40, 14, 17, 29, 43, 27, 17, 19, 8,
+        30, 19, 32, 31, 31, 32, 34, 21, 30,
+    ],
+    // 2 Tawarikh
+    &[
+        17, 18, 17, 22, 14, 42, 22, 18, 31, 19, 23, 16, 22, 15, 19, 14, 19, 34, 11, 37,
+        20, 12, 21, 27, 28, 23, 9, 27, 36, 27, 21, 33, 25, 33, 27, 23,
+    ],
+    // Ezra
+    &[11, 70, 13, 24, 17, 22, 28, 36, 15, 44],
+    // Nehemia
+    &[11, 20, 32, 23, 19, 19, 73, 18, 38, 39, 36, 47, 31],
+    // Ester
+    &[22, 23, 15, 17, 14, 14, 10, 17, 32, 3],
+    // Ayub
+    &[
+        22, 13, 26, 21, 27, 30, 21, 22, 35, 22, 20, 25, 28, 22, 35, 22, 16, 21, 29, 29,
+        34, 30, 17, 25, 6, 14, 23, 28, 25, 31, 40, 22, 33, 37, 16, 33, 24, 41, 30, 24,
+        34, 17,
+    ],
+    // Mazmur
+    &[
+        6, 12, 8, 8, 12, 10, 17, 9, 20, 18, 7, 8, 6, 7, 5, 11, 15, 50, 14, 9,
+        13, 31, 6, 10, 22, 12, 14, 9, 11, 12, 24, 11, 22, 22, 28, 12, 40, 22, 13, 17,
+        13, 11, 5, 26, 17, 11, 9, 14, 20, 23, 19, 9, 6, 7, 23, 13, 11, 11, 17, 12,
+        8, 12, 11, 10, 13, 20, 7, 35, 36, 5, 24, 20, 28, 23, 10, 12, 20, 72, 13, 19,
+        16, 8, 18, 12, 13, 17, 7, 18, 52, 17, 16, 15, 5, 23, 11, 13, 12, 9, 9, 5,
+        8, 28, 22, 35, 45, 48, 43, 13, 31, 7, 10, 10, 9, 8, 18, 19, 2, 29, 176, 7,
+        8, 9, 4, 8, 5, 6, 5, 6, 8, 8, 3, 18, 3, 3, 21, 26, 9, 8, 24, 13,
+        10, 7, 12, 15, 21, 10, 20, 14, 9, 6,
+    ],
+    // Amsal
+    &[
+        33, 22, 35, 27, 23, 35, 27, 36, 18, 32, 31, 28, 25, 35, 33, 33, 28, 24, 29, 30,
+        31, 29, 35, 34, 28, 28, 27, 28, 27, 33, 31,
+    ],
+    // Pengkhotbah
+    &[18, 26, 22, 16, 20, 12, 29, 17, 18, 20, 10, 14],
+    // Kidung Agung
+    &[17, 17, 11, 16, 16, 13, 13, 14],
+    // Yesaya
+    &[
+        31, 22, 26, 6, 30, 13, 25, 22, 21, 34, 16, 6, 22, 32, 9, 14, 14, 7, 25, 6,
+        17, 25, 18, 23, 12, 21, 13, 29, 24, 33, 9, 20, 24, 17, 10, 22, 38, 22, 8, 31,
+        29, 25, 28, 28, 25, 13, 15, 22, 26, 11, 23, 15, 12, 17, 13, 12, 21, 14, 21, 22,
+        11, 12, 19, 12, 25, 24,
+    ],
+    // Yeremia
+    &[
+        19, 37, 25, 31, 31, 30, 34, 22, 26, 25, 23, 17, 27, 22, 21, 21, 27, 23, 15, 18,
+        14, 30, 40, 10, 38, 24, 22, 17, 32, 24, 40, 44, 26, 22, 19, 32, 21, 28, 18, 16,
+        18, 22, 13, 30, 5, 28, 7, 47, 39, 46, 64, 34,
+    ],
+    // Ratapan
+    &[22, 22, 66, 22, 22],
+    // Yehezkiel
+    &[
+        28, 10, 27, 17, 17, 14, 27, 18, 11, 22, 25, 28, 23, 23, 8, 63, 24, 32, 14, 49,
+        32, 31, 49, 27, 17, 21, 36, 26, 21, 26, 18, 32, 33, 31, 15, 38, 28, 23, 29, 49,
+        26, 20, 27, 31, 25, 24, 23, 35,
+    ],
+    // Daniel
+    &[21, 49, 30, 37, 31, 28, 28, 27, 27, 21, 45, 13],
+    // Hosea
+    &[11, 23, 5, 19, 15, 11, 16, 14, 17, 15, 12, 14, 16, 9],
+    // Yoel
+    &[20, 32, 21],
+    // Amos
+    &[15, 16, 15, 13, 27, 14, 17, 14, 15],
+    // Obaja
+    &[21],
+    // Yunus
+    &[17, 10, 10, 11],
+    // Mikha
+    &[16, 13, 12, 13, 15, 16, 20],
+    // Nahum
+    &[15, 13, 19],
+    // Habakuk
+    &[17, 20, 19],
+    // Zefanya
+    &[18, 15, 20],
+    // Hagai
+    &[15, 23],
+    // Zakharia
+    &[21, 13, 10, 14, 11, 15, 14, 23, 17, 12, 17, 14, 9, 21],
+    // Maleakhi
+    &[14, 17, 18, 6],
+    // Matius
+    &[
+        25, 23, 17, 25, 48, 34, 29, 34, 38, 42, 30, 50, 58, 36, 39, 28, 27, 35, 30, 34,
+        46, 46, 39, 51, 46, 75, 66, 20,
+    ],
+    // Markus
+    &[45, 28, 35, 41, 43, 56, 37, 38, 50, 52, 33, 44, 37, 72, 47, 20],
+    // Lukas
+    &[
+        80, 52, 38, 44, 39, 49, 50, 56, 62, 42, 54, 59, 35, 35, 32, 31, 37, 43, 48, 47,
+        38, 71, 56, 53,
+    ],
+    // Yohanes
+    &[
+        51, 25, 36, 54, 47, 71, 53, 59, 41, 42, 57, 50, 38, 31, 27, 33, 26, 40, 42, 31,
+        25,
+    ],
+    // Kisah Para Rasul
+    &[
+        26, 47, 26, 37, 42, 15, 60, 40, 43, 48, 30, 25, 52, 28, 41, 40, 34, 28, 41, 38,
+        40, 30, 35, 27, 27, 32, 44, 31,
+    ],
+    // Roma
+    &[32, 29, 31, 25, 21, 23, 25, 39, 33, 21, 36, 21, 14, 23, 33, 27],
+    // 1 Korintus
+    &[31, 16, 23, 21, 13, 20, 40, 13, 27, 33, 34, 31, 13, 40, 58, 24],
+    // 2 Korintus
+    &[24, 17, 18, 18, 21, 18, 16, 24, 15, 18, 33, 21, 14],
+    // Galatia
+    &[24, 21, 29, 31, 26, 18],
+    // Efesus
+    &[23, 22, 21, 32, 33, 24],
+    // Filipi
+    &[30, 30, 21, 23],
+    // Kolose
+    &[29, 23, 25, 18],
+    // 1 Tesalonika
+    &[10, 20, 13, 18, 28],
+    // 2 Tesalonika
+    &[12, 17, 18],
+    // 1 Timotius
+    &[20, 15, 16, 16, 25, 21],
+    // 2 Timotius
+    &[18, 26, 17, 22],
+    // Titus
+    &[16, 15, 15],
+    // Filemon
+    &[25],
+    // Ibrani
+    &[14, 18, 19, 16, 14, 20, 28, 13, 28, 39, 40, 29, 25],
+    // Yakobus
+    &[27, 26, 18, 17, 20],
+    // 1 Petrus
+    &[25, 25, 22, 19, 14],
+    // 2 Petrus
+    &[21, 22, 18],
+    // 1 Yohanes
+    &[10, 29, 24, 21, 21],
+    // 2 Yohanes
+    &[13],
+    // 3 Yohanes
+    &[14],
+    // Yudas
+    &[25],
+    // Wahyu
+    &[
+        20, 29, 22, 11, 14, 17, 17, 13, 21, 11, 19, 17, 18, 20, 8, 21, 18, 24, 21, 15,
+        27, 21,
+    ],
+];
+
+/// Verse numbering a version follows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scheme {
+    /// Terjemahan Baru: Psalm superscriptions are numbered as verses, as in the Hebrew
+    /// Bible, and a few New Testament chapters follow the Greek critical text
+    Tb,
+    /// King James Version, which the English translations follow
+    Kjv,
+}
+
+impl Scheme {
+    /// Numbering used by a version: the Indonesian versions follow the TB, every other version the KJV
+    pub fn of_version(version: &str) -> Self {
+        if INDONESIAN_VERSIONS.iter().any(|v| v.eq_ignore_ascii_case(version)) {
+            Scheme::Tb
+        } else {
+            Scheme::Kjv
+        }
+    }
+}
+
+const INDONESIAN_VERSIONS: [&str; 2] = ["tb", "ayt"];
+
+/// Psalms whose superscription the TB numbers as separate verses before the KJV's verse 1:
+/// (psalm, superscription verses)
+const TB_PSALM_SUPERSCRIPTIONS: [(i32, i32); 63] = [
+    (3, 1), (4, 1), (5, 1), (6, 1), (7, 1), (8, 1), (9, 1), (12, 1), (13, 1), (18, 1),
+    (19, 1), (20, 1), (21, 1), (22, 1), (30, 1), (31, 1), (34, 1), (36, 1), (38, 1), (39, 1),
+    (40, 1), (41, 1), (42, 1), (44, 1), (45, 1), (46, 1), (47, 1), (48, 1), (49, 1), (51, 2),
+    (52, 2), (53, 1), (54, 2), (55, 1), (56, 1), (57, 1), (58, 1), (59, 1), (60, 2), (61, 1),
+    (62, 1), (63, 1), (64, 1), (65, 1), (67, 1), (68, 1), (69, 1), (70, 1), (75, 1), (76, 1),
+    (77, 1), (80, 1), (81, 1), (83, 1), (84, 1), (85, 1), (88, 1), (89, 1), (92, 1), (102, 1),
+    (108, 1), (140, 1), (142, 1),
+];
+
+/// A TB verse and the inclusive range of KJV verses it covers, if any: (book, chapter, TB verse, KJV verses)
+type VerseMapping = (&'static str, i32, i32, Option<(i32, i32)>);
+
+/// TB verses that do not map one to one onto the KJV. Verses of these chapters that are
+/// not listed keep their number.
+const TB_TO_KJV_VERSES: [VerseMapping; 4] = [
+    // The KJV splits the greeting into verses 12 and 13 and ends with the blessing as 14
+    ("2 Korintus", 13, 12, Some((12, 13))),
+    ("2 Korintus", 13, 13, Some((14, 14))),
+    // The TB splits the KJV's closing verse in two
+    ("3 Yohanes", 1, 15, Some((14, 14))),
+    // The dragon standing on the sand opens Revelation 13:1 in the KJV
+    ("Wahyu", 12, 18, None),
+];
+
+/// Chapters where the TB has a different number of verses than the superscriptions
+/// alone would give: (book, chapter, verses in TB)
+const TB_VERSE_COUNT_OVERRIDES: [(&str, i32, i32); 4] = [
+    // The KJV's verses 5 and 6 are a single verse after the superscription
+    ("Mazmur", 13, 6),
+    ("2 Korintus", 13, 13),
+    ("3 Yohanes", 1, 15),
+    ("Wahyu", 12, 18),
+];
+
+/// Number of chapters in a book, by Indonesian name. The TB and the KJV agree on every
+/// chapter boundary, Maleakhi 4 included.
+pub fn chapter_count(book: &str) -> Option<i32> {
+    book_order(book).map(|index| KJV_VERSE_COUNTS[index].len() as i32)
+}
+
+/// Number of verses a chapter has in the given version, or none for an unknown book or chapter
+pub fn verse_count(book: &str, chapter: i32, version: &str) -> Option<i32> {
+    scheme_verse_count(book, chapter, Scheme::of_version(version))
+}
+
+fn scheme_verse_count(book: &str, chapter: i32, scheme: Scheme) -> Option<i32> {
+    let index = book_order(book)?;
+    let counts = KJV_VERSE_COUNTS[index];
+    let kjv = usize::try_from(chapter).ok()?.checked_sub(1).and_then(|i| counts.get(i)).copied()?;
+    if scheme == Scheme::Kjv {
+        return Some(kjv);
+    }
+    if let Some((_, _, verses)) = TB_VERSE_COUNT_OVERRIDES.iter()
+        .find(|(name, number, _)| name.eq_ignore_ascii_case(book) && *number == chapter)
+    {
+        return Some(*verses);
+    }
+    Some(kjv + superscription_verses(book, chapter))
+}
+
+fn superscription_verses(book: &str, chapter: i32) -> i32 {
+    if !book.eq_ignore_ascii_case("Mazmur") {
+        return 0;
+    }
+    TB_PSALM_SUPERSCRIPTIONS.iter()
+        .find(|(psalm, _)| *psalm == chapter)
+        .map_or(0, |(_, verses)| *verses)
+}
+
+/// Verses in the `to` numbering covering a verse in the `from` numbering, as an inclusive
+/// range. None when the verse has no counterpart in the same chapter, such as a Psalm
+/// superscription the KJV leaves unnumbered.
+pub fn map_verse(book: &str, chapter: i32, verse: i32, from: Scheme, to: Scheme) -> Option<(i32, i32)> {
+    if verse < 1 || verse > scheme_verse_count(book, chapter, from)? {
+        return None;
+    }
+    match (from, to) {
+        (Scheme::Tb, Scheme::Kjv) => tb_to_kjv(book, chapter, verse),
+        (Scheme::Kjv, Scheme::Tb) => {
+            let tb_verses = scheme_verse_count(book, chapter, Scheme::Tb)?;
+            let covering: Vec<i32> = (1..=tb_verses)
+                .filter(|tb| tb_to_kjv(book, chapter, *tb).is_some_and(|(start, end)| start <= verse && verse <= end))
+                .collect();
+            Some((*covering.first()?, *covering.last()?))
+        }
+        _ => Some((verse, verse)),
+    }
+}
+
+fn tb_to_kjv(book: &str, chapter: i32, verse: i32) -> Option<(i32, i32)> {
+    if let Some((_, _, _, kjv)) = TB_TO_KJV_VERSES.iter()
+        .find(|(name, number, tb, _)| name.eq_ignore_ascii_case(book) && *number == chapter && *tb == verse)
+    {
+        return *kjv;
+    }
+
+    let superscription = superscription_verses(book, chapter);
+    if verse <= superscription {
+        return None;
+    }
+    let start = verse - superscription;
+    // A chapter ending with merged verses maps its last verse onto all of them
+    let end = if verse == scheme_verse_count(book, chapter, Scheme::Tb)? {
+        scheme_verse_count(book, chapter, Scheme::Kjv)?
+    } else {
+        start
+    };
+    Some((start, end))
+}
+
+/// Translates a verse selection from one numbering to another. Each range is cut to the
+/// chapter and mapped through its first and last verses that have a counterpart, so the work
+/// is bounded by the chapter's length however large the range. None when no verse has one.
+pub fn map_selection(book: &str, chapter: i32, selection: &VerseSelection, from: Scheme, to: Scheme) -> Option<VerseSelection> {
+    if from == to {
+        return Some(selection.clone());
+    }
+    let count = scheme_verse_count(book, chapter, from)?;
+    let verses: Vec<i32> = selection.ranges().iter()
+        .filter_map(|range| {
+            let verses = range.start.max(1)..=range.end.min(count);
+            let (first, _) = verses.clone().find_map(|verse| map_verse(book, chapter, verse, from, to))?;
+            let (_, last) = verses.rev().find_map(|verse| map_verse(book, chapter, verse, from, to))?;
+            Some(first..=last)
+        })
+        .flatten()
+        .collect();
+    (!verses.is_empty()).then(|| VerseSelection::from_verses(&verses))
+}
@@ -0,0 +1,52 @@
+use crate::models::bible::{BibleVersion, Testament};
+
+/// A translation served by alkitab.mobi
+#[derive(Debug, Clone, Copy)]
+pub struct KnownVersion {
+    /// Version code used in URLs and `?version=`, e.g. "tb"
+    pub code: &'static str,
+    /// Full name, as reported in `BibleChapter::version`
+    pub name: &'static str,
+    /// ISO 639-1 language code
+    pub language: &'static str,
+    /// Whether the scraper reads section titles for this version
+    pub section_titles: bool,
+}
+
+/// Every translation alkitab.mobi serves; each covers both testaments
+pub const KNOWN_VERSIONS: [KnownVersion; 8] = [
+    KnownVersion { code: "tb", name: "Alkitab Terjemahan Baru (TB)", language: "id", section_titles: true },
+    KnownVersion { code: "ayt", name: "Alkitab Yang Terbuka (AYT)", language: "id", section_titles: false },
+    KnownVersion { code: "kjv", name: "King James Version", language: "en", section_titles: false },
+    KnownVersion { code: "nkjv", name: "New King James Version", language: "en", section_titles: false },
+    KnownVersion { code: "niv", name: "New International Version", language: "en", section_titles: false },
+    KnownVersion { code: "esv", name: "English Standard Version", language: "en", section_titles: false },
+    KnownVersion { code: "nasb", name: "New American Standard Bible", language: "en", section_titles: false },
+    KnownVersion { code: "nlt", name: "New Living Translation", language: "en", section_titles: false },
+];
+
+/// Details of a known version by code
+pub fn known_version(code: &str) -> Option<&'static KnownVersion> {
+    KNOWN_VERSIONS.iter().find(|version| version.code == code)
+}
+
+/// Catalogue entry for a version code. Versions outside [`KNOWN_VERSIONS`], such as a
+/// local corpus configured under `[bible.versions]`, are described by their code alone.
+pub fn describe_version(code: &str) -> BibleVersion {
+    match known_version(code) {
+        Some(known) => BibleVersion {
+            code: known.code.to_string(),
+            name: known.name.to_string(),
+            language: Some(known.language.to_string()),
+            testaments: vec![Testament::Old, Testament::New],
+            section_titles: known.section_titles,
+        },
+        None => BibleVersion {
+            code: code.to_string(),
+            name: code.to_uppercase(),
+            language: None,
+            testaments: Vec::new(),
+            section_titles: false,
+        },
+    }
+}
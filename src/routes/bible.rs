@@ -4,5 +4,19 @@ use crate::controllers::bible;
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg
         .service(bible::find)
-        .service(bible::read);
+        .service(bible::read)
+        .service(bible::read_verses)
+        .service(bible::passage)
+        .service(bible::compare)
+        .service(bible::list_books)
+        .service(bible::list_versions)
+        .service(bible::cache_stats)
+        .service(bible::upstream_status);
+}
+
+/// Admin endpoints, mounted at `/bible/admin` behind the admin key, and only when one is configured
+pub fn admin_config(cfg: &mut web::ServiceConfig) {
+    cfg
+        .service(bible::start_prefetch)
+        .service(bible::prefetch_status);
 }
\ No newline at end of file
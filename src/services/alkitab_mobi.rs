@@ -0,0 +1,279 @@
+use regex::Regex;
+use scraper::{Html, Selector};
+use futures::future::BoxFuture;
+use crate::models::bible::{BibleChapter, Verse};
+use crate::models::book::Book;
+use crate::models::versification::verse_count;
+use crate::models::versions::{known_version, KNOWN_VERSIONS};
+use crate::services::fetcher::{FetchError, HttpFetcher, PageFetcher};
+use crate::services::retry::{AttemptError, RetryPolicy};
+use crate::services::source::ScriptureSource;
+use crate::services::validation::ChapterValidator;
+use crate::error::AppError;
+use lazy_static::lazy_static;
+use std::sync::Arc;
+
+lazy_static! {
+    static ref VERSE_NUMBER_REGEX: Regex = Regex::new(r"^(\d+)[\.\s]").unwrap();
+    static ref STRONGS_NUMBER_REGEX: Regex = Regex::new(r"< \d+ >").unwrap();
+    static ref MORPHOLOGICAL_TAG_REGEX: Regex = Regex::new(r"\(\d+\)").unwrap();
+    static ref CONTENT_NUMBER_REGEX: Regex = Regex::new(r"\s+\d+\s+").unwrap();
+}
+
+/// Scripture source that scrapes chapter pages from alkitab.mobi
+pub struct AlkitabMobiSource {
+    base_url: String,
+    fetcher: Arc<dyn PageFetcher>,
+    retry: RetryPolicy,
+    validator: Option<ChapterValidator>,
+}
+
+impl AlkitabMobiSource {
+
+    pub fn new(base_url: impl Into<String>) -> Self {
+        AlkitabMobiSource {
+            base_url: base_url.into(),
+            fetcher: Arc::new(HttpFetcher::default()),
+            retry: RetryPolicy::default(),
+            validator: Some(ChapterValidator::default()),
+        }
+    }
+
+    /// Sends requests through `client` instead of the shared `HTTP_CLIENT`
+    pub fn with_client(self, client: reqwest::Client) -> Self {
+        self.with_fetcher(Arc::new(HttpFetcher::new(client)))
+    }
+
+    /// Checks every scraped chapter with `validator`, or not at all with `None`
+    pub fn with_validator(mut self, validator: Option<ChapterValidator>) -> Self {
+        self.validator = validator;
+        self
+    }
+
+    /// Downloads pages through `fetcher`, e.g. to replay recorded pages in tests
+    pub fn with_fetcher(mut self, fetcher: Arc<dyn PageFetcher>) -> Self {
+        self.fetcher = fetcher;
+        self
+    }
+
+    /// Retries failed page requests according to `retry`
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Downloads a chapter page, retrying network errors and retryable HTTP statuses
+    async fn fetch_page(&self, url: &str) -> Result<String, AppError> {
+        self.retry.run(|| async {
+            self.fetcher.fetch(url).await.map_err(|e| match e {
+                FetchError::Network(msg) => AttemptError::Transient(AppError::ExternalService(format!("Failed to fetch chapter: {}", msg))),
+                FetchError::Status(status) => {
+                    let failure = AppError::ExternalService(format!("Failed to fetch chapter: HTTP {}", status));
+                    if self.retry.is_retryable_status(status.as_u16()) {
+                        AttemptError::Transient(failure)
+                    } else {
+                        AttemptError::Permanent(failure)
+                    }
+                }
+            })
+        }).await
+    }
+
+
+    fn clean_content(&self, content: &str, version: &str) -> String {
+        let mut cleaned = content
+            .replace("&quot;", "\"")
+            .replace("&amp;", "&")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&nbsp;", " ")
+            .replace("&apos;", "'")
+            .replace(" _", "")
+            .replace("_ ", "")
+            .replace("_", "");
+
+        // Remove verse number from the beginning of the content
+        cleaned = VERSE_NUMBER_REGEX.replace(&cleaned, "").to_string();
+
+        // For non-TB versions, clean up numbers and tags
+        if version != "tb" {
+            cleaned = STRONGS_NUMBER_REGEX.replace_all(&cleaned, "").to_string();
+            cleaned = MORPHOLOGICAL_TAG_REGEX.replace_all(&cleaned, "").to_string();
+            cleaned = CONTENT_NUMBER_REGEX.replace_all(&cleaned, " ").to_string();
+        }
+
+        // Clean up any double spaces that might have been created
+        cleaned.split_whitespace().collect::<Vec<&str>>().join(" ")
+    }
+
+    async fn scrape_chapter(&self, indonesian_book_name: &str, chapter: i32, version: &str) -> Result<BibleChapter, AppError> {
+        let short_name = match Book::from_indonesian(indonesian_book_name) {
+            Some(book) => book.path_segment,
+            None => {
+                return Err(AppError::InvalidInput(format!("Could not get short name for book: {}", indonesian_book_name)));
+            }
+        };
+
+        let version = if version.is_empty() { "tb" } else { version };
+        let version_name = known_version(version).map(|known| known.name.to_string());
+        let url = format!("{}/{}/{}/{}", self.base_url, version, short_name, chapter);
+        log::debug!("Requesting URL: {}", url);
+
+        let html = self.fetch_page(&url).await?;
+
+        let document = Html::parse_document(&html);
+        
+        let mut titles_with_ranges: Vec<String> = Vec::new();
+        let mut verses: Vec<Verse> = Vec::new();
+        
+        // Different versions have different HTML structures
+        let (verse_selector, ref_selector, content_selector) = match version {
+            "tb" | "ayt" => ("p", "span.reftext", "span[data-dur]"),
+            "kjv" | "niv" | "esv" | "nasb" | "nlt" => ("p", "span.reftext", "span[data-dur]"),
+            _ => ("p", "span.reftext", "span[data-dur]") // Default to the same selectors
+        };
+
+        if let Ok(selector) = Selector::parse(verse_selector) {
+            let mut current_title: Option<String> = None;
+            let mut current_start_verse: Option<i32> = None;
+            let mut last_verse_number: Option<i32> = None;
+            
+            for element in document.select(&selector) {
+                // Only process paragraph titles for "tb" version
+                if version == "tb"
+                    && let Some(title_span) = element.select(&Selector::parse("span.paragraphtitle").unwrap()).next()
+                    && let Some(title_text) = title_span.text().next()
+                {
+                    let title_text = title_text.trim();
+                    if !title_text.is_empty() {
+                        if let (Some(prev_title), Some(start_verse)) = (current_title.take(), current_start_verse.take())
+                            && let Some(last_verse) = last_verse_number
+                        {
+                            titles_with_ranges.push(format!("{} ({}-{})", prev_title, start_verse, last_verse));
+                        }
+                        current_title = Some(title_text.to_string());
+                    }
+                }
+
+                // Try to find verse references
+                if let Some(ref_span) = element.select(&Selector::parse(ref_selector).unwrap()).next()
+                    && let Some(verse_num_text) = ref_span.text().next()
+                {
+                    let verse_number = if let Some(captures) = VERSE_NUMBER_REGEX.captures(verse_num_text) {
+                        captures.get(1)
+                            .map(|m| m.as_str().parse::<i32>().unwrap_or(1))
+                            .unwrap_or(1)
+                    } else {
+                        verse_num_text
+                            .chars()
+                            .filter(|c| c.is_ascii_digit())
+                            .collect::<String>()
+                            .parse::<i32>()
+                            .unwrap_or(1)
+                    };
+
+                    // Only track title ranges for "tb" version
+                    if version == "tb" && current_start_verse.is_none() {
+                        current_start_verse = Some(verse_number);
+                    }
+                    
+                    // Try to find verse content
+                    let content = if let Some(content_span) = element.select(&Selector::parse(content_selector).unwrap()).next() {
+                        content_span.text().next().map(|content| content.trim().to_string())
+                    } else {
+                        // If the specific selector doesn't work, try to get the text directly from the paragraph
+                        // This is a fallback for versions with different HTML structures
+                        let text = element.text().collect::<Vec<&str>>().join(" ");
+                        if !text.trim().is_empty() {
+                            Some(text.trim().to_string())
+                        } else {
+                            None
+                        }
+                    };
+
+                    if let Some(content) = content
+                        && !content.is_empty()
+                    {
+                        let content = self.clean_content(&content, version);
+
+                        verses.push(Verse {
+                            verse: verse_number,
+                            content,
+                        });
+                        
+                        // Only track last verse number for "tb" version title ranges
+                        if version == "tb" {
+                            last_verse_number = Some(verse_number);
+                        }
+                    }
+                }
+            }
+
+            // Only finalize title ranges for "tb" version
+            if version == "tb"
+                && let (Some(title), Some(start_verse)) = (current_title, current_start_verse)
+                && let Some(last_verse) = last_verse_number
+            {
+                titles_with_ranges.push(format!("{} ({}-{})", title, start_verse, last_verse));
+            }
+        }
+
+        // An empty page for a chapter that does not exist is a plain miss, not drift
+        if verses.is_empty() && (self.validator.is_none() || verse_count(indonesian_book_name, chapter, version).is_none()) {
+            return Err(AppError::NotFound("No verses found in chapter".to_string()));
+        }
+
+        // Only add default title for "tb" version if no titles were found
+        if version == "tb" && titles_with_ranges.is_empty() {
+            titles_with_ranges.push(format!("{} {}", indonesian_book_name, chapter));
+        }
+        // For non-"tb" versions, leave titles_with_ranges empty
+
+        let parsed = BibleChapter {
+            book: vec![indonesian_book_name.to_string()],
+            chapter,
+            title: titles_with_ranges,
+            total_verses: verses.len(),
+            version: version_name,
+            verses,
+        };
+        self.check_drift(&url, indonesian_book_name, version, &parsed)?;
+        Ok(parsed)
+    }
+
+    /// Rejects a parsed chapter that fails validation, logging a `parser_drift` event
+    fn check_drift(&self, url: &str, book: &str, version: &str, chapter: &BibleChapter) -> Result<(), AppError> {
+        let Some(validator) = &self.validator else {
+            return Ok(());
+        };
+        let anomalies = validator.validate(book, version, chapter);
+        if anomalies.is_empty() {
+            return Ok(());
+        }
+
+        let summary = anomalies.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ");
+        log::error!(
+            target: "parser_drift",
+            "event=parser_drift source={} url={} version={} book=\"{}\" chapter={} verses={} anomalies=\"{}\"",
+            self.name(), url, version, book, chapter.chapter, chapter.verses.len(), summary
+        );
+        Err(AppError::ParserDrift(format!("{} {} ({}): {}", book, chapter.chapter, version, summary)))
+    }
+
+}
+
+impl ScriptureSource for AlkitabMobiSource {
+    fn name(&self) -> &'static str {
+        "alkitab_mobi"
+    }
+
+    fn fetch_chapter<'a>(&'a self, book: &'a str, chapter: i32, version: &'a str) -> BoxFuture<'a, Result<BibleChapter, AppError>> {
+        Box::pin(self.scrape_chapter(book, chapter, version))
+    }
+
+    fn list_versions(&self) -> Vec<String> {
+        let mut versions: Vec<String> = KNOWN_VERSIONS.iter().map(|v| v.code.to_string()).collect();
+        versions.sort();
+        versions
+    }
+}
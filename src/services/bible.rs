@@ -1,360 +1,333 @@
-use regex::Regex;
-use scraper::{Html, Selector};
-use crate::models::bible::{BibleChapter, BibleMetadata, Verse};
+use crate::models::bible::{parse_title_range, BibleBook, BibleChapter, BibleMetadata, BibleVersion, BookMetadata, ChapterMetadata, CacheStats, ComparedVerse, Comparison, CircuitBreakerStatus, Passage, PrefetchReport, Testament, Verse, VersionError};
+use crate::models::reference::{parse_references, ChapterSegment, ScriptureReference, SegmentVerses, MAX_PASSAGE_CHAPTERS};
+use crate::models::verse_selection::VerseSelection;
+use crate::models::versification::{self, Scheme};
+use crate::models::versions::describe_version;
+use crate::models::books::catalogue;
 use crate::error::AppError;
-use crate::config::CONFIG;
-use crate::models::book_translations::{translate_to_indonesian, get_short_name, SHORT_TO_INDONESIAN, INDONESIAN_TO_SHORT};
-use lazy_static::lazy_static;
-use std::collections::HashMap;
-use std::time::Duration;
-
-lazy_static! {
-    static ref VERSE_NUMBER_REGEX: Regex = Regex::new(r"^(\d+)[\.\s]").unwrap();
-    static ref STRONGS_NUMBER_REGEX: Regex = Regex::new(r"< \d+ >").unwrap();
-    static ref MORPHOLOGICAL_TAG_REGEX: Regex = Regex::new(r"\(\d+\)").unwrap();
-    static ref CONTENT_NUMBER_REGEX: Regex = Regex::new(r"\s+\d+\s+").unwrap();
-    static ref VERSION_NAMES: HashMap<&'static str, &'static str> = {
-        let mut m = HashMap::new();
-        m.insert("tb", "Alkitab Terjemahan Baru (TB)");
-        m.insert("ayt", "Alkitab Yang Terbuka (AYT)");
-        m.insert("kjv", "King James Version");
-        m.insert("nkjv", "New King James Version");
-        m.insert("niv", "New International Version");
-        m.insert("esv", "English Standard Version");
-        m.insert("nasb", "New American Standard Bible");
-        m.insert("nlt", "New Living Translation");
-        m
-    };
-    static ref HTTP_CLIENT: reqwest::Client = reqwest::Client::builder()
-        .pool_idle_timeout(Some(Duration::from_secs(30)))
-        .pool_max_idle_per_host(10)
-        .timeout(Duration::from_secs(10))
-        .connect_timeout(Duration::from_secs(5))
-        .build()
-        .unwrap();
+use crate::models::book::Book;
+use crate::services::cache::{CacheStatus, ChapterCache, ChapterKey, CHAPTER_CACHE};
+use crate::services::circuit_breaker::UPSTREAM_BREAKER;
+use crate::services::prefetch::{self, PrefetchOptions};
+use crate::services::source::{ScriptureSource, DEFAULT_SOURCE};
+use futures::future::join_all;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+pub struct BibleService {
+    source: Arc<dyn ScriptureSource>,
+    cache: Option<Arc<ChapterCache>>,
 }
 
-pub struct BibleService;
+impl Default for BibleService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl BibleService {
 
     pub fn new() -> Self {
-        BibleService
+        Self::with_source(DEFAULT_SOURCE.clone())
+            .with_cache(CHAPTER_CACHE.clone())
     }
 
+    /// Creates an uncached service reading from the given scripture source instead of the configured one
+    pub fn with_source(source: Arc<dyn ScriptureSource>) -> Self {
+        BibleService { source, cache: None }
+    }
 
-    pub async fn find_book_metadata(&self, book_name: &str) -> Result<BibleMetadata, AppError> {
-        let indonesian_book_name = translate_to_indonesian(book_name)
-            .ok_or_else(|| AppError::InvalidBookError(format!("Could not translate book name: {}", book_name)))?;
-        
-        let short_name = get_short_name(&indonesian_book_name)
-            .ok_or_else(|| AppError::InvalidInput(format!("Could not get short name for book: {}", indonesian_book_name)))?;
-        
-        // Special handling for Mazmur
-        let (url, pattern) = if indonesian_book_name == "Mazmur" {
-            (
-                format!("{}/tb/maz", CONFIG.bible.base_url),
-                r#"href="[^"]*?/tb/Mzm/(\d+)/"#.to_string()
-            )
-        } else {
-            (
-                format!("{}/tb/{}", CONFIG.bible.base_url, short_name.to_lowercase()),
-                format!(r#"href="[^"]*?/tb/{}/(\d+)/"#, short_name)
-            )
-        };
-        
-        let response = HTTP_CLIENT.get(&url)
-            .send()
-            .await
-            .map_err(|e| AppError::ExternalApiError(e.to_string()))?
-            .text()
-            .await
-            .map_err(|e| AppError::ExternalApiError(e.to_string()))?;
-        
-        let re = Regex::new(&pattern).unwrap();
-        let mut verses: Vec<i32> = re.captures_iter(&response)
-            .filter_map(|cap| cap.get(1))
-            .filter_map(|m| m.as_str().parse::<i32>().ok())
+    /// Serves chapters from the given cache, filling it from the source on a miss.
+    /// Concurrent misses for the same chapter share one upstream fetch.
+    pub fn with_cache(mut self, cache: Arc<ChapterCache>) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+
+    /// Every version the source can serve, in the order the source lists them
+    pub fn versions(&self) -> Vec<BibleVersion> {
+        self.source.list_versions().iter().map(|code| describe_version(code)).collect()
+    }
+
+    /// Code of a version the source serves, matched case-insensitively, with an empty
+    /// version meaning TB. Unknown versions are rejected with the list of valid codes.
+    pub fn resolve_version(&self, version: &str) -> Result<String, AppError> {
+        let version = if version.is_empty() { "tb" } else { version };
+        let versions = self.source.list_versions();
+        versions.iter()
+            .find(|code| code.eq_ignore_ascii_case(version))
+            .cloned()
+            .ok_or_else(|| AppError::InvalidInput(format!(
+                "Unknown version '{}', expected one of: {}", version, versions.join(", ")
+            )))
+    }
+
+    /// Chapters of a book in the legacy metadata shape, read from the versification table
+    pub fn find_book_metadata(&self, book_name: &str, version: &str) -> Result<BibleMetadata, AppError> {
+        self.book_metadata(book_name, version).map(|metadata| BibleMetadata::from(&metadata))
+    }
+
+    /// Chapters and verse counts of a book in the given version's versification, read from
+    /// the embedded table without asking the source
+    pub fn book_metadata(&self, book_name: &str, version: &str) -> Result<BookMetadata, AppError> {
+        let book = Book::resolve(book_name)
+            .map_err(|_| AppError::InvalidBookError(format!("Could not translate book name: {}", book_name)))?;
+        let version = &self.resolve_version(version)?;
+        let (indonesian_book_name, english_name) = (book.indonesian, book.english);
+        let testament = book.testament();
+        let order = book.index() + 1;
+
+        let chapters: Vec<ChapterMetadata> = (1..=book.chapter_count())
+            .filter_map(|chapter| {
+                versification::verse_count(indonesian_book_name, chapter, version).map(|verses| ChapterMetadata {
+                    chapter,
+                    verses,
+                    testament,
+                    order,
+                    book: indonesian_book_name.to_string(),
+                    english_name: english_name.to_string(),
+                })
+            })
             .collect();
-        verses.sort_unstable();
-        verses.dedup();
 
-        Ok(BibleMetadata {
+        Ok(BookMetadata {
             book: indonesian_book_name.to_string(),
-            total_verse: verses.len(),
-            verses,
+            english_name: english_name.to_string(),
+            testament,
+            order,
+            version: version.to_string(),
+            total_chapters: chapters.len(),
+            total_verses: chapters.iter().map(|c| c.verses as usize).sum(),
+            chapters,
         })
     }
 
+    pub async fn get_chapter(&self, book: &str, chapter: &str, version: &str) -> Result<BibleChapter, AppError> {
+        self.get_chapter_with_status(book, chapter, version).await.map(|(chapter, _)| chapter)
+    }
 
-    fn clean_content(&self, content: &str, version: &str) -> String {
-        let mut cleaned = content
-            .replace("&quot;", "\"")
-            .replace("&amp;", "&")
-            .replace("&lt;", "<")
-            .replace("&gt;", ">")
-            .replace("&nbsp;", " ")
-            .replace("&apos;", "'")
-            .replace(" _", "")
-            .replace("_ ", "")
-            .replace("_", "");
-
-        // Remove verse number from the beginning of the content
-        cleaned = VERSE_NUMBER_REGEX.replace(&cleaned, "").to_string();
-
-        // For non-TB versions, clean up numbers and tags
-        if version != "tb" {
-            cleaned = STRONGS_NUMBER_REGEX.replace_all(&cleaned, "").to_string();
-            cleaned = MORPHOLOGICAL_TAG_REGEX.replace_all(&cleaned, "").to_string();
-            cleaned = CONTENT_NUMBER_REGEX.replace_all(&cleaned, " ").to_string();
-        }
+    /// Like [`BibleService::get_chapter`], also reporting whether the chapter was served
+    /// from the cache, fetched, or served stale while it is refreshed
+    pub async fn get_chapter_with_status(&self, book: &str, chapter: &str, version: &str) -> Result<(BibleChapter, CacheStatus), AppError> {
+        let (indonesian_book_name, chapter) = self.resolve_chapter(book, chapter)?;
+        let version = &self.resolve_version(version)?;
+        check_chapter(&indonesian_book_name, chapter, version)?;
+        self.fetch_chapter(indonesian_book_name, chapter, version).await
+    }
 
-        // Clean up any double spaces that might have been created
-        cleaned.split_whitespace().collect::<Vec<&str>>().join(" ")
+    /// Resolves a book name or abbreviation to its Indonesian name and parses the chapter number
+    fn resolve_chapter(&self, book: &str, chapter: &str) -> Result<(String, i32), AppError> {
+        let indonesian_book_name = Book::resolve(book)
+            .map(|book| book.indonesian.to_string())
+            .map_err(|_| AppError::InvalidInput(format!("Could not translate book name: {}", book)))?;
+
+        let chapter = chapter.trim().parse::<i32>()
+            .map_err(|_| AppError::InvalidInput(format!("Invalid chapter number: {}", chapter)))?;
+        Ok((indonesian_book_name, chapter))
     }
 
-pub async fn get_chapter(&self, book: &str, chapter: &str, version: &str) -> Result<BibleChapter, AppError> {
-        let book_lower = book.to_lowercase();
-        let indonesian_book_name = {
-            if let Some(name) = INDONESIAN_TO_SHORT.iter()
-                .find(|(ind, _)| ind.to_lowercase() == book_lower)
-                .map(|(ind, _)| ind.to_string()) {
-                name
-            }
-            else if let Some(ind) = SHORT_TO_INDONESIAN.iter()
-                .find(|(_, short)| short.to_lowercase() == book_lower)
-                .map(|(ind, _)| ind.clone()) {
-                ind
-            }
-            else if let Some(indonesian) = match book_lower.as_str() {
-                "maz" => Some("Mazmur"),
-                "kej" => Some("Kejadian"),
-                "kel" => Some("Keluaran"),
-                "im" => Some("Imamat"),
-                "bil" => Some("Bilangan"),
-                "ula" => Some("Ulangan"),
-                "yos" => Some("Yosua"),
-                "hak" => Some("Hakim-hakim"),
-                "rut" => Some("Rut"),
-                "1sa" => Some("1 Samuel"),
-                "2sa" => Some("2 Samuel"),
-                "1ra" => Some("1 Raja-raja"),
-                "2ra" => Some("2 Raja-raja"),
-                "1ta" => Some("1 Tawarikh"),
-                "2ta" => Some("2 Tawarikh"),
-                "ezr" => Some("Ezra"),
-                "neh" => Some("Nehemia"),
-                "est" => Some("Ester"),
-                "ayb" => Some("Ayub"),
-                "ams" => Some("Amsal"),
-                "pkh" => Some("Pengkhotbah"),
-                "kid" => Some("Kidung Agung"),
-                "yes" => Some("Yesaya"),
-                "yer" => Some("Yeremia"),
-                "rat" => Some("Ratapan"),
-                "yeh" => Some("Yehezkiel"),
-                "dan" => Some("Daniel"),
-                "hos" => Some("Hosea"),
-                "yoe" => Some("Yoel"),
-                "amo" => Some("Amos"),
-                "oba" => Some("Obaja"),
-                "yun" => Some("Yunus"),
-                "mik" => Some("Mikha"),
-                "nah" => Some("Nahum"),
-                "hab" => Some("Habakuk"),
-                "zef" => Some("Zefanya"),
-                "hag" => Some("Hagai"),
-                "zak" => Some("Zakharia"),
-                "mal" => Some("Maleakhi"),
-                "mat" => Some("Matius"),
-                "mar" => Some("Markus"),
-                "luk" => Some("Lukas"),
-                "yoh" => Some("Yohanes"),
-                "kis" => Some("Kisah Para Rasul"),
-                "rom" => Some("Roma"),
-                "1ko" => Some("1 Korintus"),
-                "2ko" => Some("2 Korintus"),
-                "gal" => Some("Galatia"),
-                "efe" => Some("Efesus"),
-                "fip" => Some("Filipi"),
-                "kol" => Some("Kolose"),
-                "1te" => Some("1 Tesalonika"),
-                "2te" => Some("2 Tesalonika"),
-                "1ti" => Some("1 Timotius"),
-                "2ti" => Some("2 Timotius"),
-                "tit" => Some("Titus"),
-                "fim" => Some("Filemon"),
-                "ibr" => Some("Ibrani"),
-                "yak" => Some("Yakobus"),
-                "1pe" => Some("1 Petrus"),
-                "2pe" => Some("2 Petrus"),
-                "1yo" => Some("1 Yohanes"),
-                "2yo" => Some("2 Yohanes"),
-                "3yo" => Some("3 Yohanes"),
-                "yud" => Some("Yudas"),
-                "why" => Some("Wahyu"),
-                _ => None
-            } {
-                indonesian.to_string()
-            }
-            else if let Some(translated) = translate_to_indonesian(book) {
-                translated.to_string()
-            }
-            else {
-                return Err(AppError::InvalidInput(format!("Could not translate book name: {}", book)));
-            }
+    async fn fetch_chapter(&self, indonesian_book_name: String, chapter: i32, version: &str) -> Result<(BibleChapter, CacheStatus), AppError> {
+        let Some(cache) = &self.cache else {
+            let fetched = self.source.fetch_chapter(&indonesian_book_name, chapter, version).await?;
+            return Ok((fetched, CacheStatus::Miss));
         };
-            
-        let short_name = match get_short_name(&indonesian_book_name) {
-            Some(short) => short,
-            None => {
-                return Err(AppError::InvalidInput(format!("Could not get short name for book: {}", indonesian_book_name)));
+
+        let short_name = Book::from_indonesian(&indonesian_book_name)
+            .map_or(indonesian_book_name.as_str(), |book| book.path_segment);
+        let key = ChapterKey::new(version, short_name, chapter);
+        let source = self.source.clone();
+        let version = version.to_string();
+        cache.get_or_fetch(key, async move {
+            source.fetch_chapter(&indonesian_book_name, chapter, &version).await
+        }).await
+    }
+
+    pub async fn get_verses(&self, book: &str, chapter: &str, selection: &VerseSelection, version: &str) -> Result<BibleChapter, AppError> {
+        self.get_verses_with_status(book, chapter, selection, version).await.map(|(chapter, _)| chapter)
+    }
+
+    pub async fn get_verses_with_status(&self, book: &str, chapter: &str, selection: &VerseSelection, version: &str) -> Result<(BibleChapter, CacheStatus), AppError> {
+        let (indonesian_book_name, chapter) = self.resolve_chapter(book, chapter)?;
+        let version = &self.resolve_version(version)?;
+        check_chapter(&indonesian_book_name, chapter, version)?;
+        check_verses(&indonesian_book_name, chapter, selection)?;
+        let selection = renumber(&indonesian_book_name, chapter, selection, version)?;
+
+        let (full_chapter, status) = self.fetch_chapter(indonesian_book_name, chapter, version).await?;
+        Ok((self.select_verses(full_chapter, &selection)?, status))
+    }
+
+    /// Fetches a chapter in every version concurrently and lines their verses up by TB verse
+    /// number. A version that fails is reported in `errors` and leaves its column empty.
+    pub async fn compare(&self, book: &str, chapter: &str, versions: &[String]) -> Result<Comparison, AppError> {
+        let (indonesian_book_name, chapter) = self.resolve_chapter(book, chapter)?;
+        check_chapter(&indonesian_book_name, chapter, "tb")?;
+        let versions = versions.iter()
+            .map(|version| self.resolve_version(version))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let chapter_number = chapter.to_string();
+        let results = join_all(versions.iter().map(|version| {
+            self.get_chapter(&indonesian_book_name, &chapter_number, version)
+        }))
+        .await;
+
+        let mut columns = Vec::new();
+        let mut errors = Vec::new();
+        for (version, result) in versions.iter().zip(results) {
+            match result {
+                Ok(fetched) => columns.push(tb_numbered(&indonesian_book_name, chapter, version, fetched)),
+                Err(e) => {
+                    log::warn!("Compare {} {}: version {} failed: {}", indonesian_book_name, chapter, version, e);
+                    errors.push(VersionError { version: version.clone(), error: e.to_string() });
+                    columns.push(BTreeMap::new());
+                }
             }
-        };
+        }
 
-        let version = if version.is_empty() { "tb" } else { version };
-        let version_name = if version == "tb" {
-            Some("Alkitab Terjemahan Baru (TB)".to_string())
-        } else {
-            VERSION_NAMES.get(version).map(|&s| s.to_string())
-        };
-        let url = format!("{}/{}/{}/{}", CONFIG.bible.base_url, version, short_name, chapter);
-        println!("Requesting URL: {}", url);
+        let numbers: BTreeSet<i32> = columns.iter().flat_map(|column| column.keys().copied()).collect();
+        let verses = numbers.into_iter()
+            .map(|verse| ComparedVerse {
+                verse,
+                texts: columns.iter().map(|column| column.get(&verse).cloned()).collect(),
+            })
+            .collect();
 
-        let response = reqwest::get(&url).await.map_err(|_e| {
-            AppError::ExternalService("Failed to fetch chapter".to_string())
-        })?;
+        Ok(Comparison {
+            book: indonesian_book_name,
+            chapter,
+            versions,
+            verses,
+            errors,
+        })
+    }
 
-        if !response.status().is_success() {
-            return Err(AppError::ExternalService(format!(
-                "Failed to fetch chapter: HTTP {}",
-                response.status()
+    /// Fetches every chapter involved in the references and returns the passages in order.
+    ///
+    /// All references together may span at most `MAX_PASSAGE_CHAPTERS` chapters, and at most
+    /// `PASSAGE_FETCH_CONCURRENCY` of them are fetched at once.
+    pub async fn get_passages(&self, references: &[ScriptureReference], version: &str) -> Result<Vec<Passage>, AppError> {
+        let version = &self.resolve_version(version)?;
+        let planned = references.iter()
+            .map(|reference| Ok((reference, self.passage_segments(reference)?)))
+            .collect::<Result<Vec<_>, AppError>>()?;
+        if planned.iter().map(|(_, segments)| segments.len()).sum::<usize>() > MAX_PASSAGE_CHAPTERS {
+            return Err(AppError::InvalidInput(format!(
+                "References span more than {} chapters", MAX_PASSAGE_CHAPTERS
             )));
         }
 
-        let html = response.text().await.map_err(|_e| {
-            AppError::ExternalService("Failed to get response text".to_string())
-        })?;
-
-        let document = Html::parse_document(&html);
-        
-        let mut titles_with_ranges: Vec<String> = Vec::new();
-        let mut verses: Vec<Verse> = Vec::new();
-        
-        // Different versions have different HTML structures
-        let (verse_selector, ref_selector, content_selector) = match version {
-            "tb" | "ayt" => ("p", "span.reftext", "span[data-dur]"),
-            "kjv" | "niv" | "esv" | "nasb" | "nlt" => ("p", "span.reftext", "span[data-dur]"),
-            _ => ("p", "span.reftext", "span[data-dur]") // Default to the same selectors
-        };
+        let fetches: Vec<_> = planned.iter()
+            .flat_map(|(_, segments)| segments.iter())
+            .map(|segment| self.fetch_segment(segment, version))
+            .collect();
+        let chapters: Vec<BibleChapter> = stream::iter(fetches)
+            .buffered(PASSAGE_FETCH_CONCURRENCY)
+            .try_collect()
+            .await?;
+
+        let mut chapters = chapters.into_iter();
+        Ok(planned.iter()
+            .map(|(reference, segments)| Passage {
+                reference: reference.to_string(),
+                chapters: chapters.by_ref().take(segments.len()).collect(),
+            })
+            .collect())
+    }
 
-        if let Some(selector) = Selector::parse(verse_selector).ok() {
-            let mut current_title: Option<String> = None;
-            let mut current_start_verse: Option<i32> = None;
-            let mut last_verse_number: Option<i32> = None;
-            
-            for element in document.select(&selector) {
-                // Only process paragraph titles for "tb" version
-                if version == "tb" {
-                    if let Some(title_span) = element.select(&Selector::parse("span.paragraphtitle").unwrap()).next() {
-                        if let Some(title_text) = title_span.text().next() {
-                            let title_text = title_text.trim();
-                            if !title_text.is_empty() {
-                                if let (Some(prev_title), Some(start_verse)) = (current_title.take(), current_start_verse.take()) {
-                                    if let Some(last_verse) = last_verse_number {
-                                        titles_with_ranges.push(format!("{} ({}-{})", prev_title, start_verse, last_verse));
-                                    }
-                                }
-                                current_title = Some(title_text.to_string());
-                            }
-                        }
-                    }
-                }
+    /// Resolves a reference into its chapter segments, which may cross chapter and book boundaries
+    pub async fn get_passage(&self, reference: &ScriptureReference, version: &str) -> Result<Passage, AppError> {
+        let mut passages = self.get_passages(std::slice::from_ref(reference), version).await?;
+        Ok(passages.remove(0))
+    }
 
-                // Try to find verse references
-                if let Some(ref_span) = element.select(&Selector::parse(ref_selector).unwrap()).next() {
-                    if let Some(verse_num_text) = ref_span.text().next() {
-                        let verse_number = if let Some(captures) = VERSE_NUMBER_REGEX.captures(verse_num_text) {
-                            captures.get(1)
-                                .map(|m| m.as_str().parse::<i32>().unwrap_or(1))
-                                .unwrap_or(1)
-                        } else {
-                            verse_num_text
-                                .chars()
-                                .filter(|c| c.is_digit(10))
-                                .collect::<String>()
-                                .parse::<i32>()
-                                .unwrap_or(1)
-                        };
-
-                        // Only track title ranges for "tb" version
-                        if version == "tb" && current_start_verse.is_none() {
-                            current_start_verse = Some(verse_number);
-                        }
-                        
-                        // Try to find verse content
-                        let content = if let Some(content_span) = element.select(&Selector::parse(content_selector).unwrap()).next() {
-                            if let Some(content) = content_span.text().next() {
-                                Some(content.trim().to_string())
-                            } else {
-                                None
-                            }
-                        } else {
-                            // If the specific selector doesn't work, try to get the text directly from the paragraph
-                            // This is a fallback for versions with different HTML structures
-                            let text = element.text().collect::<Vec<&str>>().join(" ");
-                            if !text.trim().is_empty() {
-                                Some(text.trim().to_string())
-                            } else {
-                                None
-                            }
-                        };
-
-                        if let Some(content) = content {
-                            if !content.is_empty() {
-                                let content = self.clean_content(&content, version);
-
-                                verses.push(Verse {
-                                    verse: verse_number,
-                                    content,
-                                });
-                                
-                                // Only track last verse number for "tb" version title ranges
-                                if version == "tb" {
-                                    last_verse_number = Some(verse_number);
-                                }
-                            }
-                        }
-                    }
-                }
+    /// Expands a reference into chapter segments, rejecting verses past the end of a chapter
+    fn passage_segments(&self, reference: &ScriptureReference) -> Result<Vec<ChapterSegment>, AppError> {
+        let chapter_counts: HashMap<&'static str, i32> = reference.books_needing_chapter_counts()
+            .into_iter()
+            .map(|book| Ok((book, self.chapter_count(book)?)))
+            .collect::<Result<_, AppError>>()?;
+
+        let segments = reference.segments(&chapter_counts)?;
+        for segment in &segments {
+            match &segment.verses {
+                SegmentVerses::All => {}
+                SegmentVerses::Selection(selection) => check_verses(segment.book, segment.chapter, selection)?,
+                SegmentVerses::From(start) => check_verses(segment.book, segment.chapter, &VerseSelection::range(*start, *start))?,
             }
+        }
+        Ok(segments)
+    }
 
-            // Only finalize title ranges for "tb" version
-            if version == "tb" {
-                if let (Some(title), Some(start_verse)) = (current_title, current_start_verse) {
-                    if let Some(last_verse) = last_verse_number {
-                        titles_with_ranges.push(format!("{} ({}-{})", title, start_verse, last_verse));
-                    }
-                }
+    async fn fetch_segment(&self, segment: &ChapterSegment, version: &str) -> Result<BibleChapter, AppError> {
+        let chapter = self.get_chapter(segment.book, &segment.chapter.to_string(), version).await?;
+        match &segment.verses {
+            SegmentVerses::All => Ok(chapter),
+            SegmentVerses::Selection(selection) => {
+                self.select_verses(chapter, &renumber(segment.book, segment.chapter, selection, version)?)
+            }
+            SegmentVerses::From(from) => {
+                // A start the version leaves unnumbered, like a Psalm superscription, moves to
+                // the next verse it does number
+                let count = versification::verse_count(segment.book, segment.chapter, "tb").unwrap_or(*from);
+                let start = (*from..=count)
+                    .find_map(|verse| versification::map_verse(segment.book, segment.chapter, verse, Scheme::Tb, Scheme::of_version(version)))
+                    .map(|(first, _)| first)
+                    .ok_or_else(|| AppError::NotFound(format!(
+                        "{} {}:{} has no counterpart in {}", segment.book, segment.chapter, from, version
+                    )))?;
+                let last = chapter.verses.iter().map(|v| v.verse).max().unwrap_or(start);
+                self.select_verses(chapter, &VerseSelection::range(start, last.max(start)))
             }
         }
+    }
 
-        if verses.is_empty() {
-            return Err(AppError::NotFound("No verses found in chapter".to_string()));
-        }
+    fn chapter_count(&self, book: &str) -> Result<i32, AppError> {
+        versification::chapter_count(book)
+            .ok_or_else(|| AppError::NotFound(format!("No chapters found for {}", book)))
+    }
 
-        // Only add default title for "tb" version if no titles were found
-        if version == "tb" && titles_with_ranges.is_empty() {
-            titles_with_ranges.push(format!("{} {}", indonesian_book_name, chapter));
+    /// Trims a chapter down to the selected verses, narrowing the title ranges to match
+    pub fn select_verses(&self, chapter: BibleChapter, selection: &VerseSelection) -> Result<BibleChapter, AppError> {
+        if let Some(missing) = selection.verses().find(|v| !chapter.verses.iter().any(|verse| verse.verse == *v)) {
+            return Err(AppError::NotFound(format!(
+                "Verse {} not found in {} {}",
+                missing,
+                chapter.book.join(", "),
+                chapter.chapter
+            )));
         }
-        // For non-"tb" versions, leave titles_with_ranges empty
+
+        let verses: Vec<Verse> = chapter.verses.into_iter()
+            .filter(|v| selection.contains(v.verse))
+            .collect();
+
+        let title = chapter.title.iter()
+            .filter_map(|title| {
+                if let Some((text, start, end)) = parse_title_range(title) {
+                    let selected: Vec<i32> = verses.iter()
+                        .map(|v| v.verse)
+                        .filter(|v| (start..=end).contains(v))
+                        .collect();
+                    if selected.is_empty() {
+                        return None;
+                    }
+                    Some(format!("{} ({})", text, VerseSelection::from_verses(&selected)))
+                } else {
+                    // Titles without a range cover the whole chapter, e.g. "Kejadian 1"
+                    Some(format!("{}:{}", title, selection))
+                }
+            })
+            .collect();
 
         Ok(BibleChapter {
-            book: vec![indonesian_book_name],
-            chapter: chapter.parse().unwrap_or(1),
-            title: titles_with_ranges,
+            book: chapter.book,
+            chapter: chapter.chapter,
+            title,
             total_verses: verses.len(),
-            version: version_name,
+            version: chapter.version,
             verses,
         })
     }
@@ -376,14 +349,134 @@ pub async fn get_chapter(&self, book: &str, chapter: &str, version: &str) -> Res
 
 }
 
-pub async fn find(book: &str) -> Result<BibleMetadata, AppError> {
+/// Rejects chapters the book does not have before anything is fetched. Books missing from
+/// the versification table are left for the source to decide.
+fn check_chapter(book: &str, chapter: i32, version: &str) -> Result<(), AppError> {
+    match versification::chapter_count(book) {
+        Some(count) if versification::verse_count(book, chapter, version).is_none() => Err(AppError::NotFound(format!(
+            "Chapter {} not found in {}, which has {} chapter{}", chapter, book, count, if count == 1 { "" } else { "s" }
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Rejects verses past the end of a chapter before anything is fetched or renumbered. Verse
+/// numbers are given in TB numbering, whatever the version.
+fn check_verses(book: &str, chapter: i32, selection: &VerseSelection) -> Result<(), AppError> {
+    if let Some(expected) = versification::verse_count(book, chapter, "tb")
+        && let Some(missing) = selection.verses().find(|v| *v < 1 || *v > expected)
+    {
+        return Err(AppError::NotFound(format!("Verse {} not found in {} {}", missing, book, chapter)));
+    }
+    Ok(())
+}
+
+/// Verse texts of a chapter keyed by TB verse number. Verses of a version numbered like the
+/// KJV are moved to their TB numbers, joining verses the TB counts as one.
+fn tb_numbered(book: &str, chapter: i32, version: &str, fetched: BibleChapter) -> BTreeMap<i32, String> {
+    let scheme = Scheme::of_version(version);
+    let mut texts: BTreeMap<i32, String> = BTreeMap::new();
+    for verse in fetched.verses {
+        let (start, end) = versification::map_verse(book, chapter, verse.verse, scheme, Scheme::Tb)
+            .unwrap_or((verse.verse, verse.verse));
+        for number in start..=end {
+            texts.entry(number)
+                .and_modify(|text| {
+                    text.push(' ');
+                    text.push_str(&verse.content);
+                })
+                .or_insert_with(|| verse.content.clone());
+        }
+    }
+    texts
+}
+
+/// Renumbers a selection written in TB numbering into the numbering of `version`,
+/// so a reference points at the same text in every version
+fn renumber(book: &str, chapter: i32, selection: &VerseSelection, version: &str) -> Result<VerseSelection, AppError> {
+    versification::map_selection(book, chapter, selection, Scheme::Tb, Scheme::of_version(version))
+        .ok_or_else(|| AppError::NotFound(format!(
+            "{} {}:{} has no counterpart in {}", book, chapter, selection, version
+        )))
+}
+
+pub async fn find(book: &str, version: &str) -> Result<BookMetadata, AppError> {
     let bible_service = BibleService::new();
     let formatted_book_name = bible_service.format_book_name(book.trim())?;
-    bible_service.find_book_metadata(&formatted_book_name).await
+    bible_service.book_metadata(&formatted_book_name, version)
 }
 
-pub async fn read(book: &str, chapter: i32, version: &str) -> Result<BibleChapter, AppError> {
+pub async fn read(book: &str, chapter: i32, version: &str) -> Result<(BibleChapter, CacheStatus), AppError> {
     let bible_service = BibleService::new();
     let formatted_book_name = bible_service.format_book_name(book.trim())?;
-    bible_service.get_chapter(&formatted_book_name, &chapter.to_string(), version).await
-} 
\ No newline at end of file
+    bible_service.get_chapter_with_status(&formatted_book_name, &chapter.to_string(), version).await
+}
+
+pub async fn read_verses(book: &str, chapter: i32, verses: &str, version: &str) -> Result<(BibleChapter, CacheStatus), AppError> {
+    let selection = VerseSelection::parse(verses)?;
+    let bible_service = BibleService::new();
+    let formatted_book_name = bible_service.format_book_name(book.trim())?;
+    bible_service.get_verses_with_status(&formatted_book_name, &chapter.to_string(), &selection, version).await
+}
+
+/// Most chapters fetched at once for one passage request
+pub const PASSAGE_FETCH_CONCURRENCY: usize = 8;
+
+/// Largest number of versions one comparison may ask for
+pub const MAX_COMPARE_VERSIONS: usize = 8;
+
+pub async fn compare(book: &str, chapter: i32, versions: &str) -> Result<Comparison, AppError> {
+    let mut requested: Vec<String> = Vec::new();
+    for version in versions.split(',').map(|v| v.trim().to_lowercase()).filter(|v| !v.is_empty()) {
+        if !requested.contains(&version) {
+            requested.push(version);
+        }
+    }
+    if requested.is_empty() {
+        return Err(AppError::InvalidInput("No versions to compare".to_string()));
+    }
+    if requested.len() > MAX_COMPARE_VERSIONS {
+        return Err(AppError::InvalidInput(format!(
+            "At most {} versions can be compared at once", MAX_COMPARE_VERSIONS
+        )));
+    }
+
+    let bible_service = BibleService::new();
+    let formatted_book_name = bible_service.format_book_name(book.trim())?;
+    bible_service.compare(&formatted_book_name, &chapter.to_string(), &requested).await
+}
+
+pub async fn passage(reference: &str, version: &str) -> Result<Vec<Passage>, AppError> {
+    let references = parse_references(reference)?;
+    let bible_service = BibleService::new();
+    bible_service.get_passages(&references, version).await
+}
+
+pub fn books(testament: Option<&str>) -> Result<Vec<BibleBook>, AppError> {
+    let testament = testament.map(str::parse::<Testament>).transpose()?;
+    Ok(catalogue(testament))
+}
+
+pub fn versions() -> Vec<BibleVersion> {
+    BibleService::new().versions()
+}
+
+pub fn cache_stats() -> CacheStats {
+    CHAPTER_CACHE.stats()
+}
+
+pub fn upstream_status() -> CircuitBreakerStatus {
+    UPSTREAM_BREAKER.status()
+}
+
+pub fn start_prefetch(version: &str, concurrency: Option<usize>, delay_ms: Option<u64>) -> Result<PrefetchReport, AppError> {
+    let version = version.trim();
+    if version.is_empty() {
+        return Err(AppError::InvalidInput("Empty version".to_string()));
+    }
+    prefetch::start(PrefetchOptions::from_request(version, concurrency, delay_ms))
+}
+
+pub fn prefetch_status() -> Result<PrefetchReport, AppError> {
+    prefetch::status().ok_or_else(|| AppError::NotFound("No prefetch has been started".to_string()))
+}
\ No newline at end of file
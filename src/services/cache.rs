@@ -0,0 +1,223 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use futures::future::{BoxFuture, FutureExt, Shared};
+use lazy_static::lazy_static;
+use crate::config::{CacheConfig, CONFIG};
+use crate::error::AppError;
+use crate::models::bible::{BibleChapter, CacheStats};
+
+type SharedFetch = Shared<BoxFuture<'static, Result<BibleChapter, AppError>>>;
+
+/// Identifies a cached chapter: version code, alkitab.mobi book short name and chapter number
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ChapterKey {
+    pub version: String,
+    pub book: String,
+    pub chapter: i32,
+}
+
+impl ChapterKey {
+    pub fn new(version: &str, book: &str, chapter: i32) -> Self {
+        ChapterKey {
+            version: version.to_lowercase(),
+            book: book.to_string(),
+            chapter,
+        }
+    }
+}
+
+struct CacheEntry {
+    chapter: BibleChapter,
+    inserted_at: Instant,
+    last_used: Instant,
+}
+
+/// Where a chapter returned by [`ChapterCache::get_or_fetch`] came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheStatus {
+    /// Served from the cache within its TTL
+    Hit,
+    /// Fetched from the scripture source
+    Miss,
+    /// Served from the cache after its TTL passed, while a refresh runs in the background
+    Stale,
+}
+
+impl CacheStatus {
+    /// Value of the `X-Cache-Status` response header
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CacheStatus::Hit => "hit",
+            CacheStatus::Miss => "miss",
+            CacheStatus::Stale => "stale",
+        }
+    }
+}
+
+/// A size-bounded cache of parsed chapters shared by every `BibleService`.
+///
+/// Entries become stale `ttl` after they were stored but are kept until evicted, so
+/// they can still be served while a refresh runs or when the upstream is failing.
+/// When the cache is full the least recently used entry is evicted. A zero TTL or
+/// zero capacity disables caching.
+///
+/// Concurrent misses for the same key are coalesced by [`ChapterCache::get_or_fetch`]
+/// into a single upstream fetch, even when caching is disabled.
+pub struct ChapterCache {
+    ttl: Duration,
+    max_entries: usize,
+    entries: Mutex<HashMap<ChapterKey, CacheEntry>>,
+    in_flight: Mutex<HashMap<ChapterKey, SharedFetch>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    stale: AtomicU64,
+}
+
+impl ChapterCache {
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        ChapterCache {
+            ttl,
+            max_entries,
+            entries: Mutex::new(HashMap::new()),
+            in_flight: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            stale: AtomicU64::new(0),
+        }
+    }
+
+    pub fn from_config(config: &CacheConfig) -> Self {
+        Self::new(Duration::from_secs(config.ttl_seconds), config.max_entries)
+    }
+
+    fn enabled(&self) -> bool {
+        !self.ttl.is_zero() && self.max_entries > 0
+    }
+
+    /// Returns a copy of the cached chapter and whether it is still fresh, counting
+    /// the lookup as a hit, a stale hit or a miss
+    pub fn lookup(&self, key: &ChapterKey) -> Option<(BibleChapter, CacheStatus)> {
+        if !self.enabled() {
+            return None;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        let now = Instant::now();
+        let found = entries.get_mut(key).map(|entry| {
+            entry.last_used = now;
+            let status = if now.duration_since(entry.inserted_at) < self.ttl {
+                CacheStatus::Hit
+            } else {
+                CacheStatus::Stale
+            };
+            (entry.chapter.clone(), status)
+        });
+
+        let counter = match &found {
+            Some((_, CacheStatus::Hit)) => &self.hits,
+            Some(_) => &self.stale,
+            None => &self.misses,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        found
+    }
+
+    pub fn insert(&self, key: ChapterKey, chapter: BibleChapter) {
+        if !self.enabled() {
+            return;
+        }
+
+        let mut entries = self.entries.lock().unwrap();
+        if !entries.contains_key(&key)
+            && entries.len() >= self.max_entries
+            && let Some(oldest) = entries.iter().min_by_key(|(_, entry)| entry.last_used).map(|(key, _)| key.clone())
+        {
+            entries.remove(&oldest);
+        }
+        let now = Instant::now();
+        entries.insert(key, CacheEntry { chapter, inserted_at: now, last_used: now });
+    }
+
+    /// Returns the cached chapter, or runs `fetch` and caches its result.
+    ///
+    /// While a fetch for `key` is in flight, later callers wait for it instead of
+    /// starting their own, and every waiter receives the same chapter or the same error.
+    /// Errors are not cached.
+    ///
+    /// A stale chapter is returned immediately and refreshed in the background. If the
+    /// refresh fails the stale chapter is kept and served again by later requests.
+    pub async fn get_or_fetch<F>(self: &Arc<Self>, key: ChapterKey, fetch: F) -> Result<(BibleChapter, CacheStatus), AppError>
+    where
+        F: Future<Output = Result<BibleChapter, AppError>> + Send + 'static,
+    {
+        match self.lookup(&key) {
+            Some((chapter, CacheStatus::Stale)) => {
+                let refresh = {
+                    let mut in_flight = self.in_flight.lock().unwrap();
+                    if in_flight.contains_key(&key) {
+                        None
+                    } else {
+                        let shared = fetch.boxed().shared();
+                        in_flight.insert(key.clone(), shared.clone());
+                        Some(shared)
+                    }
+                };
+                if let Some(shared) = refresh {
+                    let cache = self.clone();
+                    tokio::spawn(async move {
+                        let description = format!("{} {} {}", key.version, key.book, key.chapter);
+                        if let Err(e) = cache.finish_fetch(key, shared).await {
+                            log::warn!("Refreshing stale chapter {} failed, keeping the stale copy: {}", description, e);
+                        }
+                    });
+                }
+                return Ok((chapter, CacheStatus::Stale));
+            }
+            Some(found) => return Ok(found),
+            None => {}
+        }
+
+        let shared = self.in_flight.lock().unwrap()
+            .entry(key.clone())
+            .or_insert_with(|| fetch.boxed().shared())
+            .clone();
+        self.finish_fetch(key, shared).await.map(|chapter| (chapter, CacheStatus::Miss))
+    }
+
+    async fn finish_fetch(&self, key: ChapterKey, shared: SharedFetch) -> Result<BibleChapter, AppError> {
+        let result = shared.clone().await;
+
+        // The first waiter to finish retires the fetch. Any waiter may do this, so a
+        // fetch still completes and is cleaned up if the caller that started it goes away.
+        let finished = {
+            let mut in_flight = self.in_flight.lock().unwrap();
+            let current = in_flight.get(&key).is_some_and(|pending| pending.ptr_eq(&shared));
+            if current {
+                in_flight.remove(&key);
+            }
+            current
+        };
+        if finished && let Ok(chapter) = &result {
+            self.insert(key, chapter.clone());
+        }
+        result
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            stale_hits: self.stale.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.entries.lock().unwrap().len(),
+            max_entries: self.max_entries,
+            ttl_seconds: self.ttl.as_secs(),
+        }
+    }
+}
+
+lazy_static! {
+    pub static ref CHAPTER_CACHE: Arc<ChapterCache> = Arc::new(ChapterCache::from_config(&CONFIG.bible.cache));
+}
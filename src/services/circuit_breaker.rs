@@ -0,0 +1,191 @@
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use futures::future::BoxFuture;
+use lazy_static::lazy_static;
+use crate::config::{CircuitBreakerConfig, CONFIG};
+use crate::error::AppError;
+use crate::models::bible::{BibleChapter, CircuitBreakerStatus, CircuitState};
+use crate::services::source::ScriptureSource;
+
+struct BreakerState {
+    consecutive_failures: u32,
+    /// Set while the circuit is open or half-open
+    opened_at: Option<Instant>,
+    /// Whether a half-open probe is in flight
+    probing: bool,
+    last_error: Option<String>,
+}
+
+/// Stops sending requests to a failing upstream.
+///
+/// The circuit opens after `failure_threshold` consecutive upstream failures. While
+/// open, calls fail immediately with [`AppError::ServiceUnavailable`] instead of
+/// waiting for timeouts. Once `cool_down` has passed the circuit is half-open: a single
+/// probe call goes through while others keep failing fast. A successful probe closes
+/// the circuit, a failed one opens it for another cool-down.
+///
+/// Only upstream errors (`ExternalApiError`, `ExternalService`) count as failures; a
+/// missing chapter or bad input shows the upstream is answering.
+pub struct CircuitBreaker {
+    failure_threshold: u32,
+    cool_down: Duration,
+    state: Mutex<BreakerState>,
+}
+
+/// Permission to make one call; clears the probe flag if the call is abandoned
+struct Permit<'a> {
+    breaker: &'a CircuitBreaker,
+    probe: bool,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        if self.probe {
+            self.breaker.state.lock().unwrap().probing = false;
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub fn new(failure_threshold: u32, cool_down: Duration) -> Self {
+        CircuitBreaker {
+            failure_threshold: failure_threshold.max(1),
+            cool_down,
+            state: Mutex::new(BreakerState {
+                consecutive_failures: 0,
+                opened_at: None,
+                probing: false,
+                last_error: None,
+            }),
+        }
+    }
+
+    pub fn from_config(config: &CircuitBreakerConfig) -> Self {
+        Self::new(config.failure_threshold, Duration::from_secs(config.cool_down_seconds))
+    }
+
+    /// Runs `call` unless the circuit is open, recording whether the upstream failed
+    pub async fn call<T, F>(&self, call: F) -> Result<T, AppError>
+    where
+        F: Future<Output = Result<T, AppError>>,
+    {
+        let permit = self.acquire()?;
+        let result = call.await;
+        self.record(&result, permit.probe);
+        result
+    }
+
+    fn acquire(&self) -> Result<Permit<'_>, AppError> {
+        let mut state = self.state.lock().unwrap();
+        let Some(opened_at) = state.opened_at else {
+            return Ok(Permit { breaker: self, probe: false });
+        };
+
+        let open_for = opened_at.elapsed();
+        if open_for >= self.cool_down && !state.probing {
+            state.probing = true;
+            log::info!("Circuit half-open, probing the upstream");
+            return Ok(Permit { breaker: self, probe: true });
+        }
+
+        Err(AppError::ServiceUnavailable {
+            message: format!(
+                "Upstream unavailable after {} consecutive failures: {}",
+                state.consecutive_failures,
+                state.last_error.as_deref().unwrap_or("unknown error")
+            ),
+            retry_after_secs: retry_after(self.cool_down.saturating_sub(open_for)),
+        })
+    }
+
+    fn record<T>(&self, result: &Result<T, AppError>, probe: bool) {
+        let mut state = self.state.lock().unwrap();
+        match result {
+            Err(e) if is_upstream_failure(e) => {
+                state.consecutive_failures += 1;
+                state.last_error = Some(e.to_string());
+                if probe || (state.opened_at.is_none() && state.consecutive_failures >= self.failure_threshold) {
+                    log::warn!(
+                        "Circuit open after {} consecutive upstream failures, failing fast for {:?}: {}",
+                        state.consecutive_failures, self.cool_down, e
+                    );
+                    state.opened_at = Some(Instant::now());
+                }
+            }
+            _ => {
+                if state.opened_at.is_some() {
+                    log::info!("Upstream recovered, circuit closed");
+                }
+                state.consecutive_failures = 0;
+                state.opened_at = None;
+                state.last_error = None;
+            }
+        }
+    }
+
+    pub fn status(&self) -> CircuitBreakerStatus {
+        let state = self.state.lock().unwrap();
+        let (circuit, retry_after_seconds) = match state.opened_at {
+            None => (CircuitState::Closed, None),
+            Some(opened_at) => {
+                let remaining = self.cool_down.saturating_sub(opened_at.elapsed());
+                if remaining.is_zero() {
+                    (CircuitState::HalfOpen, None)
+                } else {
+                    (CircuitState::Open, Some(retry_after(remaining)))
+                }
+            }
+        };
+        CircuitBreakerStatus {
+            state: circuit,
+            consecutive_failures: state.consecutive_failures,
+            failure_threshold: self.failure_threshold,
+            cool_down_seconds: self.cool_down.as_secs(),
+            retry_after_seconds,
+            last_error: state.last_error.clone(),
+        }
+    }
+}
+
+/// Scripture source wrapper sending every chapter request through a circuit breaker.
+///
+/// It wraps the remote source itself, beneath the disk cache, so chapters served from
+/// disk or a local corpus neither fail fast nor count as upstream successes.
+pub struct CircuitBreakerSource {
+    inner: Arc<dyn ScriptureSource>,
+    breaker: Arc<CircuitBreaker>,
+}
+
+impl CircuitBreakerSource {
+    pub fn new(inner: Arc<dyn ScriptureSource>, breaker: Arc<CircuitBreaker>) -> Self {
+        CircuitBreakerSource { inner, breaker }
+    }
+}
+
+impl ScriptureSource for CircuitBreakerSource {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn fetch_chapter<'a>(&'a self, book: &'a str, chapter: i32, version: &'a str) -> BoxFuture<'a, Result<BibleChapter, AppError>> {
+        Box::pin(self.breaker.call(self.inner.fetch_chapter(book, chapter, version)))
+    }
+
+    fn list_versions(&self) -> Vec<String> {
+        self.inner.list_versions()
+    }
+}
+
+fn is_upstream_failure(error: &AppError) -> bool {
+    matches!(error, AppError::ExternalApiError(_) | AppError::ExternalService(_))
+}
+
+/// Whole seconds until `remaining` has passed, at least 1
+fn retry_after(remaining: Duration) -> u64 {
+    remaining.as_secs_f64().ceil().max(1.0) as u64
+}
+
+lazy_static! {
+    pub static ref UPSTREAM_BREAKER: Arc<CircuitBreaker> = Arc::new(CircuitBreaker::from_config(&CONFIG.bible.circuit_breaker));
+}
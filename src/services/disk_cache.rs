@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use futures::future::BoxFuture;
+use crate::error::AppError;
+use crate::models::bible::BibleChapter;
+use crate::models::book::Book;
+use crate::services::fetcher::sanitize_path_segment;
+use crate::services::source::ScriptureSource;
+
+/// Layout version of the cached chapter files. Bump it whenever the parsers change
+/// what they produce, so chapters written by an older build are no longer served.
+pub const DISK_CACHE_SCHEMA_VERSION: u32 = 1;
+
+/// Scripture source wrapper persisting every fetched chapter as JSON on disk.
+///
+/// Files live at `<root>/v<schema>/<version>/<book short name>/<chapter>.json` and
+/// survive restarts and redeploys. Cached chapters do not expire; only a schema bump
+/// or removing the directory invalidates them. Chapters of other schema versions are
+/// never read, but never deleted either. Read and write failures are logged
+/// and fall back to the wrapped source.
+pub struct DiskCachedSource {
+    inner: Arc<dyn ScriptureSource>,
+    dir: PathBuf,
+}
+
+impl DiskCachedSource {
+    /// Wraps `inner`, creating the schema directory under `root`. Directories of other
+    /// schema versions are left alone, since `root` may hold more than this cache.
+    pub fn new(inner: Arc<dyn ScriptureSource>, root: impl AsRef<Path>) -> Result<Self, AppError> {
+        let root = root.as_ref();
+        let dir = root.join(format!("v{}", DISK_CACHE_SCHEMA_VERSION));
+        fs::create_dir_all(&dir)
+            .map_err(|e| AppError::InvalidInput(format!("Could not create disk cache {}: {}", dir.display(), e)))?;
+
+        if let Ok(entries) = fs::read_dir(root) {
+            for entry in entries.filter_map(Result::ok) {
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_string();
+                if path != dir && path.is_dir() && is_schema_dir(&name) {
+                    log::warn!("Ignoring disk cache {} from another schema version; remove it to free the space", path.display());
+                }
+            }
+        }
+
+        Ok(DiskCachedSource { inner, dir })
+    }
+
+    fn chapter_path(&self, book: &str, chapter: i32, version: &str) -> PathBuf {
+        let book = Book::from_indonesian(book).map_or(book, |book| book.path_segment);
+        self.dir
+            .join(sanitize_path_segment(&version.to_lowercase()))
+            .join(sanitize_path_segment(book))
+            .join(format!("{}.json", chapter))
+    }
+
+    async fn read(&self, path: &Path) -> Option<BibleChapter> {
+        let content = tokio::fs::read(path).await.ok()?;
+        match serde_json::from_slice(&content) {
+            Ok(chapter) => Some(chapter),
+            Err(e) => {
+                log::warn!("Ignoring unreadable disk cache entry {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+
+    async fn write(&self, path: &Path, chapter: &BibleChapter) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        // Write to a temporary file first so readers never see a partial chapter
+        let temporary = path.with_extension(format!("json.{}.tmp", std::process::id()));
+        tokio::fs::write(&temporary, serde_json::to_vec(chapter)?).await?;
+        tokio::fs::rename(&temporary, path).await
+    }
+}
+
+impl ScriptureSource for DiskCachedSource {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn fetch_chapter<'a>(&'a self, book: &'a str, chapter: i32, version: &'a str) -> BoxFuture<'a, Result<BibleChapter, AppError>> {
+        Box::pin(async move {
+            let path = self.chapter_path(book, chapter, version);
+            if let Some(cached) = self.read(&path).await {
+                return Ok(cached);
+            }
+
+            let fetched = self.inner.fetch_chapter(book, chapter, version).await?;
+            if let Err(e) = self.write(&path, &fetched).await {
+                log::warn!("Could not write disk cache entry {}: {}", path.display(), e);
+            }
+            Ok(fetched)
+        })
+    }
+
+    fn list_versions(&self) -> Vec<String> {
+        self.inner.list_versions()
+    }
+}
+
+fn is_schema_dir(name: &str) -> bool {
+    name.strip_prefix('v').is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
@@ -0,0 +1,158 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use futures::future::BoxFuture;
+use reqwest::StatusCode;
+use crate::services::http_client::{describe_error, HTTP_CLIENT};
+
+/// Environment variable switching [`fixture_fetcher`] from replaying to recording
+pub const RECORD_FIXTURES_ENV: &str = "ALKITAB_RECORD_FIXTURES";
+
+/// Why a page could not be downloaded
+#[derive(Debug, Clone)]
+pub enum FetchError {
+    /// The request or the response body failed, e.g. a refused connection or a timeout
+    Network(String),
+    /// The upstream answered with a non-success status
+    Status(StatusCode),
+}
+
+/// Downloads upstream pages by URL.
+///
+/// `AlkitabMobiSource` parses whatever its fetcher returns, so swapping the fetcher
+/// lets tests replay recorded pages instead of reaching alkitab.mobi.
+pub trait PageFetcher: Send + Sync {
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, FetchError>>;
+}
+
+/// Fetches pages over HTTP
+pub struct HttpFetcher {
+    client: reqwest::Client,
+}
+
+impl HttpFetcher {
+    pub fn new(client: reqwest::Client) -> Self {
+        HttpFetcher { client }
+    }
+}
+
+impl Default for HttpFetcher {
+    fn default() -> Self {
+        Self::new(HTTP_CLIENT.clone())
+    }
+}
+
+impl PageFetcher for HttpFetcher {
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, FetchError>> {
+        Box::pin(async move {
+            let response = self.client.get(url)
+                .send()
+                .await
+                .map_err(|e| FetchError::Network(describe_error(&e)))?;
+
+            let status = response.status();
+            if !status.is_success() {
+                return Err(FetchError::Status(status));
+            }
+
+            response.text()
+                .await
+                .map_err(|e| FetchError::Network(format!("Failed to read response body: {}", describe_error(&e))))
+        })
+    }
+}
+
+/// Serves pages from files recorded under `dir`, without any network access.
+///
+/// A URL's path maps to `<dir>/<path>.html`, so `https://alkitab.mobi/tb/Kej/1` is read
+/// from `<dir>/tb/Kej/1.html`. Pages without a file are answered with a 404.
+pub struct FixtureFetcher {
+    dir: PathBuf,
+}
+
+impl FixtureFetcher {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FixtureFetcher { dir: dir.into() }
+    }
+}
+
+impl PageFetcher for FixtureFetcher {
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, FetchError>> {
+        Box::pin(async move {
+            let path = fixture_path(&self.dir, url)?;
+            match tokio::fs::read_to_string(&path).await {
+                Ok(page) => Ok(page),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    log::debug!("No fixture recorded for {} at {}", url, path.display());
+                    Err(FetchError::Status(StatusCode::NOT_FOUND))
+                }
+                Err(e) => Err(FetchError::Network(format!("Could not read fixture {}: {}", path.display(), e))),
+            }
+        })
+    }
+}
+
+/// Fetches pages through `inner` and saves every successful page as a fixture under `dir`
+pub struct RecordingFetcher {
+    inner: Arc<dyn PageFetcher>,
+    dir: PathBuf,
+}
+
+impl RecordingFetcher {
+    pub fn new(inner: Arc<dyn PageFetcher>, dir: impl Into<PathBuf>) -> Self {
+        RecordingFetcher { inner, dir: dir.into() }
+    }
+}
+
+impl PageFetcher for RecordingFetcher {
+    fn fetch<'a>(&'a self, url: &'a str) -> BoxFuture<'a, Result<String, FetchError>> {
+        Box::pin(async move {
+            let page = self.inner.fetch(url).await?;
+            let path = fixture_path(&self.dir, url)?;
+            if let Some(parent) = path.parent() {
+                tokio::fs::create_dir_all(parent).await
+                    .map_err(|e| FetchError::Network(format!("Could not create {}: {}", parent.display(), e)))?;
+            }
+            tokio::fs::write(&path, &page).await
+                .map_err(|e| FetchError::Network(format!("Could not record fixture {}: {}", path.display(), e)))?;
+            log::info!("Recorded {} to {}", url, path.display());
+            Ok(page)
+        })
+    }
+}
+
+/// Replays the fixtures in `dir`, or re-records them from the live site when
+/// `ALKITAB_RECORD_FIXTURES` is set to a non-empty value other than "0"
+pub fn fixture_fetcher(dir: impl AsRef<Path>) -> Arc<dyn PageFetcher> {
+    let dir = dir.as_ref();
+    let recording = env::var(RECORD_FIXTURES_ENV).is_ok_and(|value| !value.is_empty() && value != "0");
+    if recording {
+        Arc::new(RecordingFetcher::new(Arc::new(HttpFetcher::default()), dir))
+    } else {
+        Arc::new(FixtureFetcher::new(dir))
+    }
+}
+
+/// File holding the recorded page for `url`
+pub fn fixture_path(dir: &Path, url: &str) -> Result<PathBuf, FetchError> {
+    let parsed = reqwest::Url::parse(url)
+        .map_err(|e| FetchError::Network(format!("Invalid URL {}: {}", url, e)))?;
+    let segments: Vec<String> = parsed.path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).map(sanitize_path_segment).collect())
+        .unwrap_or_default();
+    let Some((file, parents)) = segments.split_last() else {
+        return Ok(dir.join("index.html"));
+    };
+
+    let mut path = dir.to_path_buf();
+    path.extend(parents);
+    Ok(path.join(format!("{}.html", file)))
+}
+
+/// Replaces everything but letters, digits, `-` and `_` so a URL segment or
+/// user-supplied code cannot escape the directory it is joined to
+pub(crate) fn sanitize_path_segment(segment: &str) -> String {
+    segment.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect()
+}
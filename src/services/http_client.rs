@@ -0,0 +1,49 @@
+use std::error::Error;
+use std::time::Duration;
+use lazy_static::lazy_static;
+use crate::config::{HttpClientConfig, CONFIG};
+use crate::error::AppError;
+
+/// Builds an HTTP client for upstream requests from `[bible.http]`-style settings
+pub fn build_client(config: &HttpClientConfig) -> Result<reqwest::Client, AppError> {
+    let mut builder = reqwest::Client::builder()
+        .timeout(Duration::from_secs(config.timeout_seconds))
+        .connect_timeout(Duration::from_secs(config.connect_timeout_seconds))
+        .pool_idle_timeout(Some(Duration::from_secs(config.pool_idle_timeout_seconds)))
+        .pool_max_idle_per_host(config.pool_max_idle_per_host)
+        .user_agent(config.user_agent.as_str());
+
+    if let Some(proxy) = &config.proxy {
+        let proxy = reqwest::Proxy::all(proxy.as_str())
+            .map_err(|e| AppError::InvalidInput(format!("Invalid HTTP proxy {}: {}", proxy, describe_error(&e))))?;
+        builder = builder.proxy(proxy);
+    }
+
+    builder.build()
+        .map_err(|e| AppError::InvalidInput(format!("Could not build HTTP client: {}", describe_error(&e))))
+}
+
+/// Formats an error followed by its chain of causes, e.g.
+/// "error sending request for url (...): client error (Connect): Connection refused".
+///
+/// reqwest only prints the outermost layer, which hides why a request failed.
+pub fn describe_error(error: &dyn Error) -> String {
+    let mut description = error.to_string();
+    let mut cause = error.source();
+    while let Some(inner) = cause {
+        let message = inner.to_string();
+        // Some layers repeat their cause in their own message
+        if !description.ends_with(&message) {
+            description.push_str(": ");
+            description.push_str(&message);
+        }
+        cause = inner.source();
+    }
+    description
+}
+
+lazy_static! {
+    /// Pooled client shared by every upstream source without its own `http` settings
+    pub static ref HTTP_CLIENT: reqwest::Client = build_client(&CONFIG.bible.http)
+        .expect("Failed to build HTTP client");
+}
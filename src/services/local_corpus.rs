@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::path::Path;
+use futures::future::BoxFuture;
+use crate::error::AppError;
+use crate::import::{load_corpus, CorpusFormat};
+use crate::models::bible::BibleChapter;
+use crate::models::book::Book;
+use crate::services::source::ScriptureSource;
+
+/// Scripture source serving a whole translation from memory.
+///
+/// The corpus is loaded once, either from a JSON file holding an array of chapters
+/// in the same shape as the `/bible/read` response or from one of the formats in
+/// [`CorpusFormat`]. Book names may be spelled any way [`Book::resolve`] accepts and
+/// are stored under their Indonesian names, which is how chapters are looked up. Like
+/// scraped chapters, untitled TB chapters are titled "<book> <chapter>".
+pub struct LocalCorpusSource {
+    version: String,
+    chapters: HashMap<(String, i32), BibleChapter>,
+}
+
+impl LocalCorpusSource {
+
+    pub fn new(version: impl Into<String>, chapters: Vec<BibleChapter>) -> Result<Self, AppError> {
+        let version = version.into();
+        let mut index = HashMap::new();
+        for mut chapter in chapters {
+            let name = chapter.book.first()
+                .ok_or_else(|| AppError::InvalidInput(format!(
+                    "Chapter {} in the {} corpus has no book name", chapter.chapter, version
+                )))?;
+            let book = Book::resolve(name)
+                .map_err(|e| AppError::InvalidInput(format!(
+                    "Chapter {} in the {} corpus: {}", chapter.chapter, version, e
+                )))?
+                .indonesian
+                .to_string();
+            chapter.book[0] = book.clone();
+            if version.eq_ignore_ascii_case("tb") && chapter.title.is_empty() {
+                chapter.title.push(format!("{} {}", book, chapter.chapter));
+            }
+            if index.contains_key(&(book.clone(), chapter.chapter)) {
+                return Err(AppError::InvalidInput(format!(
+                    "Duplicate chapter {} {} in the {} corpus", book, chapter.chapter, version
+                )));
+            }
+            index.insert((book, chapter.chapter), chapter);
+        }
+
+        Ok(LocalCorpusSource { version, chapters: index })
+    }
+
+    /// Loads a JSON corpus file for the given version
+    pub fn load(version: impl Into<String>, path: impl AsRef<Path>) -> Result<Self, AppError> {
+        Self::load_format(version, path, CorpusFormat::Json, None)
+    }
+
+    /// Loads a corpus file, or a directory of files, in the given format.
+    /// `name` is used as the chapters' version name for formats that do not carry one.
+    pub fn load_format(version: impl Into<String>, path: impl AsRef<Path>, format: CorpusFormat, name: Option<&str>) -> Result<Self, AppError> {
+        let path = path.as_ref();
+        let chapters = load_corpus(path, format, name)?;
+        let source = Self::new(version, chapters)?;
+        log::info!("Loaded {} chapters of {} from {}", source.chapters.len(), source.version, path.display());
+        Ok(source)
+    }
+}
+
+impl ScriptureSource for LocalCorpusSource {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    fn fetch_chapter<'a>(&'a self, book: &'a str, chapter: i32, _version: &'a str) -> BoxFuture<'a, Result<BibleChapter, AppError>> {
+        let result = self.chapters.get(&(book.to_string(), chapter))
+            .cloned()
+            .ok_or_else(|| AppError::NotFound(format!("{} {} is not in the {} corpus", book, chapter, self.version)));
+        Box::pin(async move { result })
+    }
+
+    fn list_versions(&self) -> Vec<String> {
+        vec![self.version.clone()]
+    }
+}
@@ -1 +1,12 @@
-pub mod bible; 
\ No newline at end of file
+pub mod bible;
+pub mod source;
+pub mod alkitab_mobi;
+pub mod local_corpus;
+pub mod cache;
+pub mod disk_cache;
+pub mod prefetch;
+pub mod retry;
+pub mod circuit_breaker;
+pub mod http_client;
+pub mod fetcher;
+pub mod validation;
\ No newline at end of file
@@ -0,0 +1,168 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::stream::{self, StreamExt};
+use lazy_static::lazy_static;
+use crate::config::CONFIG;
+use crate::error::AppError;
+use crate::models::bible::{PrefetchFailure, PrefetchReport};
+use crate::models::book::BOOKS;
+use crate::services::bible::BibleService;
+use crate::services::source::DEFAULT_SOURCE;
+
+/// Upper bound for the number of chapters fetched at once
+pub const MAX_PREFETCH_CONCURRENCY: usize = 16;
+
+pub const DEFAULT_PREFETCH_CONCURRENCY: usize = 4;
+
+pub const DEFAULT_PREFETCH_DELAY_MS: u64 = 250;
+
+/// Shortest pause a prefetch requested over HTTP may use, so it cannot hammer the upstream
+pub const MIN_PREFETCH_DELAY_MS: u64 = 100;
+
+/// How a prefetch run walks the Bible
+#[derive(Debug, Clone)]
+pub struct PrefetchOptions {
+    /// Version code to fetch, e.g. "tb"
+    pub version: String,
+    /// Number of chapters fetched at once
+    pub concurrency: usize,
+    /// Pause before each upstream request, per worker
+    pub delay: Duration,
+}
+
+impl PrefetchOptions {
+    /// Options for a prefetch requested over HTTP: missing values get their defaults
+    /// and the delay is raised to at least [`MIN_PREFETCH_DELAY_MS`]
+    pub fn from_request(version: &str, concurrency: Option<usize>, delay_ms: Option<u64>) -> Self {
+        PrefetchOptions {
+            version: version.to_lowercase(),
+            concurrency: concurrency.unwrap_or(DEFAULT_PREFETCH_CONCURRENCY),
+            delay: Duration::from_millis(delay_ms.unwrap_or(DEFAULT_PREFETCH_DELAY_MS).max(MIN_PREFETCH_DELAY_MS)),
+        }
+    }
+}
+
+/// Progress of a prefetch run, updated as chapters complete
+pub struct PrefetchProgress {
+    report: Mutex<PrefetchReport>,
+}
+
+impl PrefetchProgress {
+    pub fn new(version: &str) -> Self {
+        PrefetchProgress {
+            report: Mutex::new(PrefetchReport {
+                version: version.to_string(),
+                running: true,
+                books: 0,
+                total_chapters: 0,
+                fetched: 0,
+                failed: 0,
+                failures: Vec::new(),
+            }),
+        }
+    }
+
+    pub fn snapshot(&self) -> PrefetchReport {
+        self.report.lock().unwrap().clone()
+    }
+
+    fn update(&self, change: impl FnOnce(&mut PrefetchReport)) {
+        change(&mut self.report.lock().unwrap());
+    }
+
+    fn fail(&self, book: &str, chapter: i32, error: &AppError) {
+        self.update(|report| {
+            report.failed += 1;
+            report.failures.push(PrefetchFailure {
+                book: book.to_string(),
+                chapter,
+                error: error.to_string(),
+            });
+        });
+    }
+}
+
+/// Fetches every chapter of every book through `service`, filling its caches.
+///
+/// Books come from `BOOKS` in canonical order and their chapters from the
+/// versification table. At most `options.concurrency` requests run at once and each
+/// waits `options.delay` first, to stay polite to the upstream. Failures are recorded
+/// per chapter and do not stop the run.
+pub async fn prefetch(service: &BibleService, options: &PrefetchOptions, progress: &PrefetchProgress) {
+    let concurrency = options.concurrency.clamp(1, MAX_PREFETCH_CONCURRENCY);
+    progress.update(|report| report.books = BOOKS.len());
+
+    let chapters: Vec<(&'static str, i32)> = BOOKS.iter()
+        .flat_map(|book| (1..=book.chapter_count()).map(move |chapter| (book.indonesian, chapter)))
+        .collect();
+    progress.update(|report| report.total_chapters = chapters.len());
+
+    // Futures are collected first: building them inside stream combinators trips up
+    // the `Send` check when the run is spawned
+    let fetches: Vec<_> = chapters.into_iter()
+        .map(|(book, chapter)| fetch_chapter(service, options, progress, book, chapter))
+        .collect();
+    stream::iter(fetches).buffer_unordered(concurrency).collect::<Vec<()>>().await;
+
+    progress.update(|report| report.running = false);
+    let report = progress.snapshot();
+    log::info!(
+        "Prefetch {} finished: {} of {} chapters fetched, {} failures",
+        report.version, report.fetched, report.total_chapters, report.failed
+    );
+}
+
+async fn fetch_chapter(service: &BibleService, options: &PrefetchOptions, progress: &PrefetchProgress, book: &'static str, chapter: i32) {
+    tokio::time::sleep(options.delay).await;
+    match service.get_chapter(book, &chapter.to_string(), &options.version).await {
+        Ok(_) => {
+            progress.update(|report| report.fetched += 1);
+            log::debug!("Prefetch {}: fetched {} {}", options.version, book, chapter);
+        }
+        Err(e) => {
+            log::warn!("Prefetch {}: {} {} failed: {}", options.version, book, chapter, e);
+            progress.fail(book, chapter, &e);
+        }
+    }
+}
+
+lazy_static! {
+    static ref CURRENT_JOB: Mutex<Option<Arc<PrefetchProgress>>> = Mutex::new(None);
+}
+
+/// Starts a background prefetch filling the disk cache, unless one is already running.
+///
+/// The chapters bypass the in-memory cache, which is too small to hold a whole version
+/// and would lose its working set to the prefetch. Without a disk cache there is
+/// nowhere to keep them, so the prefetch is refused.
+pub fn start(mut options: PrefetchOptions) -> Result<PrefetchReport, AppError> {
+    if CONFIG.bible.disk_cache.is_none() {
+        return Err(AppError::InvalidInput("Prefetch needs a disk cache, configure [bible.disk_cache]".to_string()));
+    }
+    let service = BibleService::with_source(DEFAULT_SOURCE.clone());
+    options.version = service.resolve_version(&options.version)?;
+    let mut current = CURRENT_JOB.lock().unwrap();
+    if let Some(job) = current.as_ref() {
+        let report = job.snapshot();
+        if report.running {
+            return Err(AppError::InvalidInput(format!(
+                "A prefetch of version {} is already running", report.version
+            )));
+        }
+    }
+
+    let progress = Arc::new(PrefetchProgress::new(&options.version));
+    *current = Some(progress.clone());
+    let report = progress.snapshot();
+
+    log::info!("Starting prefetch of version {}", options.version);
+    tokio::spawn(async move {
+        prefetch(&service, &options, &progress).await;
+    });
+    Ok(report)
+}
+
+/// Progress of the running or most recent prefetch
+pub fn status() -> Option<PrefetchReport> {
+    CURRENT_JOB.lock().unwrap().as_ref().map(|job| job.snapshot())
+}
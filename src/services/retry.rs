@@ -0,0 +1,116 @@
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::BuildHasher;
+use std::time::Duration;
+use crate::config::RetryConfig;
+use crate::error::AppError;
+
+/// Outcome of a single failed attempt
+#[derive(Debug)]
+pub enum AttemptError {
+    /// Worth trying again, e.g. a timeout or a 502
+    Transient(AppError),
+    /// Retrying would not help, e.g. a 404
+    Permanent(AppError),
+}
+
+/// How often and how patiently upstream requests are retried.
+///
+/// Attempt `n` (counting from 1) that fails transiently is followed by a pause of
+/// `base_delay * 2^(n-1)`, capped at `max_delay` and shortened by a random fraction
+/// of up to `jitter` so that many clients do not retry in lockstep.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    /// Between 0 (no jitter) and 1 (anywhere from no pause to the full backoff)
+    pub jitter: f64,
+    /// HTTP status codes that are retried
+    pub retryable_statuses: Vec<u16>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::from_config(&RetryConfig::default())
+    }
+}
+
+impl RetryPolicy {
+    pub fn from_config(config: &RetryConfig) -> Self {
+        RetryPolicy {
+            max_attempts: config.max_attempts.max(1),
+            base_delay: Duration::from_millis(config.base_delay_ms),
+            max_delay: Duration::from_millis(config.max_delay_ms),
+            jitter: config.jitter.clamp(0.0, 1.0),
+            retryable_statuses: config.retryable_statuses.clone(),
+        }
+    }
+
+    /// A policy making a single attempt
+    pub fn none() -> Self {
+        RetryPolicy {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    pub fn is_retryable_status(&self, status: u16) -> bool {
+        self.retryable_statuses.contains(&status)
+    }
+
+    /// Pause before the attempt following failed attempt number `attempt`
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponential = self.base_delay.saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        let capped = exponential.min(self.max_delay);
+        capped.mul_f64(1.0 - self.jitter * random_fraction())
+    }
+
+    /// Runs `operation` until it succeeds, fails permanently or runs out of attempts.
+    ///
+    /// When more than one attempt was made, the final error message says how many.
+    pub async fn run<T, F, Fut>(&self, mut operation: F) -> Result<T, AppError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, AttemptError>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation().await {
+                Ok(value) => return Ok(value),
+                Err(AttemptError::Transient(error)) if attempt < self.max_attempts => {
+                    let delay = self.backoff(attempt);
+                    log::warn!("Attempt {} of {} failed, retrying in {:?}: {}", attempt, self.max_attempts, delay, error);
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(AttemptError::Transient(error) | AttemptError::Permanent(error)) => {
+                    return Err(if attempt > 1 { with_attempts(error, attempt) } else { error });
+                }
+            }
+        }
+    }
+}
+
+/// Appends the number of attempts made to the error message
+fn with_attempts(error: AppError, attempts: u32) -> AppError {
+    let suffix = |msg: String| format!("{} (after {} attempts)", msg, attempts);
+    match error {
+        AppError::ExternalApiError(msg) => AppError::ExternalApiError(suffix(msg)),
+        AppError::InvalidBookError(msg) => AppError::InvalidBookError(suffix(msg)),
+        AppError::InvalidInput(msg) => AppError::InvalidInput(suffix(msg)),
+        AppError::ExternalService(msg) => AppError::ExternalService(suffix(msg)),
+        AppError::NotFound(msg) => AppError::NotFound(suffix(msg)),
+        AppError::ParserDrift(msg) => AppError::ParserDrift(suffix(msg)),
+        AppError::ServiceUnavailable { message, retry_after_secs } => AppError::ServiceUnavailable {
+            message: suffix(message),
+            retry_after_secs,
+        },
+    }
+}
+
+/// A pseudo-random number in [0, 1), good enough for spreading out retries
+fn random_fraction() -> f64 {
+    let bits = RandomState::new().hash_one(std::time::Instant::now());
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use futures::future::BoxFuture;
+use lazy_static::lazy_static;
+use crate::config::{BibleConfig, HttpClientConfig, VersionSourceConfig, CONFIG};
+use crate::error::AppError;
+use crate::models::bible::BibleChapter;
+use crate::services::alkitab_mobi::AlkitabMobiSource;
+use crate::services::circuit_breaker::{CircuitBreakerSource, UPSTREAM_BREAKER};
+use crate::services::disk_cache::DiskCachedSource;
+use crate::services::http_client::{build_client, HTTP_CLIENT};
+use crate::services::local_corpus::LocalCorpusSource;
+use crate::services::retry::RetryPolicy;
+use crate::services::validation::ChapterValidator;
+
+/// A provider of Bible text.
+///
+/// Books are always passed by their Indonesian name (e.g. "Kejadian"); resolving
+/// user input to that name is the job of `BibleService`.
+pub trait ScriptureSource: Send + Sync {
+    /// Identifier used in configuration and logs, e.g. "alkitab_mobi"
+    fn name(&self) -> &'static str;
+
+    /// Fetches a single chapter of a book in the given version
+    fn fetch_chapter<'a>(&'a self, book: &'a str, chapter: i32, version: &'a str) -> BoxFuture<'a, Result<BibleChapter, AppError>>;
+
+    /// Lists the version codes this source can serve
+    fn list_versions(&self) -> Vec<String>;
+}
+
+/// Dispatches each request to the source configured for its version, falling back to a default source
+pub struct VersionRoutedSource {
+    default: Arc<dyn ScriptureSource>,
+    versions: HashMap<String, Arc<dyn ScriptureSource>>,
+}
+
+impl VersionRoutedSource {
+    pub fn new(default: Arc<dyn ScriptureSource>, versions: HashMap<String, Arc<dyn ScriptureSource>>) -> Self {
+        VersionRoutedSource { default, versions }
+    }
+
+    fn source_for(&self, version: &str) -> &Arc<dyn ScriptureSource> {
+        self.versions.get(version).unwrap_or(&self.default)
+    }
+}
+
+impl ScriptureSource for VersionRoutedSource {
+    fn name(&self) -> &'static str {
+        "routed"
+    }
+
+    fn fetch_chapter<'a>(&'a self, book: &'a str, chapter: i32, version: &'a str) -> BoxFuture<'a, Result<BibleChapter, AppError>> {
+        self.source_for(version).fetch_chapter(book, chapter, version)
+    }
+
+    fn list_versions(&self) -> Vec<String> {
+        let mut versions = self.default.list_versions();
+        versions.extend(self.versions.keys().cloned());
+        versions.sort();
+        versions.dedup();
+        versions
+    }
+}
+
+/// Builds a remote source; `http` replaces the shared client settings when given
+fn build_source(kind: &str, config: &BibleConfig, http: Option<&HttpClientConfig>) -> Result<Arc<dyn ScriptureSource>, AppError> {
+    let client = match http {
+        Some(http) => build_client(http)?,
+        None => HTTP_CLIENT.clone(),
+    };
+    let source: Arc<dyn ScriptureSource> = match kind {
+        "alkitab_mobi" => Arc::new(
+            AlkitabMobiSource::new(config.base_url.clone())
+                .with_client(client)
+                .with_retry(RetryPolicy::from_config(&config.retry))
+                .with_validator(config.validation.enabled.then(|| ChapterValidator::from_config(&config.validation)))
+        ),
+        other => return Err(AppError::InvalidInput(format!("Unknown scripture source: {}", other))),
+    };
+
+    // Only requests that reach the upstream go through the circuit breaker; the disk
+    // cache on top keeps serving stored chapters while the circuit is open
+    let source: Arc<dyn ScriptureSource> = Arc::new(CircuitBreakerSource::new(source, UPSTREAM_BREAKER.clone()));
+
+    // Remote sources are backed by the disk cache so a restart does not re-scrape everything
+    match &config.disk_cache {
+        Some(disk_cache) => Ok(Arc::new(DiskCachedSource::new(source, &disk_cache.path)?)),
+        None => Ok(source),
+    }
+}
+
+fn build_version_source(version: &str, version_config: &VersionSourceConfig, config: &BibleConfig) -> Result<Arc<dyn ScriptureSource>, AppError> {
+    if version_config.source != "local" {
+        return build_source(&version_config.source, config, version_config.http.as_ref());
+    }
+
+    let path = version_config.path.as_deref().ok_or_else(|| AppError::InvalidInput(format!(
+        "Missing corpus path for local source of version {}", version
+    )))?;
+    Ok(Arc::new(LocalCorpusSource::load_format(
+        version,
+        path,
+        version_config.format,
+        version_config.name.as_deref(),
+    )?))
+}
+
+/// Builds the scripture source selected by `bible.source`, with the per-version
+/// overrides from `bible.versions` layered on top
+pub fn from_config(config: &BibleConfig) -> Result<Arc<dyn ScriptureSource>, AppError> {
+    let default = build_source(&config.source, config, None)?;
+    if config.versions.is_empty() {
+        return Ok(default);
+    }
+
+    let mut versions = HashMap::new();
+    for (version, version_config) in &config.versions {
+        let source = build_version_source(version, version_config, config)?;
+        versions.insert(version.clone(), source);
+    }
+
+    Ok(Arc::new(VersionRoutedSource::new(default, versions)))
+}
+
+lazy_static! {
+    pub static ref DEFAULT_SOURCE: Arc<dyn ScriptureSource> = from_config(&CONFIG.bible)
+        .expect("Failed to initialize scripture source");
+}
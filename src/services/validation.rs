@@ -0,0 +1,96 @@
+use std::collections::HashSet;
+use std::fmt;
+use crate::config::ValidationConfig;
+use crate::models::bible::BibleChapter;
+use crate::models::versification::verse_count;
+
+/// Something about a scraped chapter that a correct parse would not produce
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Anomaly {
+    /// No verses were found in a chapter that exists
+    NoVerses,
+    /// A verse was found without any text
+    EmptyContent { verse: i32 },
+    /// The same verse number was found more than once
+    DuplicateVerse { verse: i32 },
+    /// A verse number was lower than the one before it
+    OutOfOrder { previous: i32, verse: i32 },
+    /// A verse number past the end of the chapter
+    UnexpectedVerse { verse: i32, expected: i32 },
+    /// Fewer verses than the chapter has, beyond the tolerance
+    MissingVerses { found: usize, expected: i32 },
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Anomaly::NoVerses => write!(f, "no verses found"),
+            Anomaly::EmptyContent { verse } => write!(f, "verse {} is empty", verse),
+            Anomaly::DuplicateVerse { verse } => write!(f, "verse {} appears more than once", verse),
+            Anomaly::OutOfOrder { previous, verse } => write!(f, "verse {} follows verse {}", verse, previous),
+            Anomaly::UnexpectedVerse { verse, expected } => write!(f, "verse {} is past the expected {} verses", verse, expected),
+            Anomaly::MissingVerses { found, expected } => write!(f, "found {} of {} expected verses", found, expected),
+        }
+    }
+}
+
+/// Checks scraped chapters against the expected verse counts, to notice when the
+/// upstream markup changes and the scraper starts returning partial or garbled chapters.
+///
+/// Versions differ slightly in versification and some omit disputed verses, so the
+/// verse count may be off by up to `tolerance` before it is reported.
+#[derive(Debug, Clone)]
+pub struct ChapterValidator {
+    tolerance: i32,
+}
+
+impl Default for ChapterValidator {
+    fn default() -> Self {
+        Self::from_config(&ValidationConfig::default())
+    }
+}
+
+impl ChapterValidator {
+    pub fn new(tolerance: i32) -> Self {
+        ChapterValidator { tolerance: tolerance.max(0) }
+    }
+
+    pub fn from_config(config: &ValidationConfig) -> Self {
+        Self::new(config.verse_count_tolerance)
+    }
+
+    /// Lists everything wrong with a parsed chapter of `book` (Indonesian name); empty when it looks right
+    pub fn validate(&self, book: &str, version: &str, chapter: &BibleChapter) -> Vec<Anomaly> {
+        let mut anomalies = Vec::new();
+        if chapter.verses.is_empty() {
+            anomalies.push(Anomaly::NoVerses);
+            return anomalies;
+        }
+
+        let mut seen = HashSet::new();
+        let mut previous: Option<i32> = None;
+        for verse in &chapter.verses {
+            if verse.content.trim().is_empty() {
+                anomalies.push(Anomaly::EmptyContent { verse: verse.verse });
+            }
+            if !seen.insert(verse.verse) {
+                anomalies.push(Anomaly::DuplicateVerse { verse: verse.verse });
+            } else if let Some(previous) = previous
+                && verse.verse < previous
+            {
+                anomalies.push(Anomaly::OutOfOrder { previous, verse: verse.verse });
+            }
+            previous = Some(verse.verse);
+        }
+
+        if let Some(expected) = verse_count(book, chapter.chapter, version) {
+            if let Some(verse) = chapter.verses.iter().map(|v| v.verse).find(|v| *v > expected + self.tolerance) {
+                anomalies.push(Anomaly::UnexpectedVerse { verse, expected });
+            }
+            if (seen.len() as i32) < expected - self.tolerance {
+                anomalies.push(Anomaly::MissingVerses { found: seen.len(), expected });
+            }
+        }
+        anomalies
+    }
+}
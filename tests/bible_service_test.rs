@@ -1,20 +1,26 @@
-use alkitab_api_rust::services::bible::BibleService;
+use alkitab_api_rust::services::fetcher::fixture_path;
 use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::models::bible::{BibleMetadata, ChapterMetadata, Testament};
+use alkitab_api_rust::models::verse_selection::VerseSelection;
+
+mod common;
+
+use common::fixture_service;
 
 #[tokio::test]
 async fn test_find_book_metadata() {
-    let service = BibleService::new();
-    let result = service.find_book_metadata("Kejadian").await;
+    let service = fixture_service();
+    let result = service.find_book_metadata("Kejadian", "tb");
     assert!(result.is_ok());
     let metadata = result.unwrap();
     assert_eq!(metadata.book, "Kejadian");
-    assert!(metadata.total_verse > 0);
-    assert!(!metadata.verses.is_empty());
+    assert_eq!(metadata.total_verse, 50);
+    assert_eq!(metadata.verses, (1..=50).collect::<Vec<i32>>());
 }
 
 #[tokio::test]
 async fn test_get_chapter() {
-    let service = BibleService::new();
+    let service = fixture_service();
     let result = service.get_chapter("Kejadian", "1", "tb").await;
     assert!(result.is_ok());
     let chapter = result.unwrap();
@@ -25,9 +31,50 @@ async fn test_get_chapter() {
     assert!(!chapter.verses.is_empty());
 }
 
+#[tokio::test]
+async fn test_get_chapter_tb_parses_titles_and_verses() {
+    let chapter = fixture_service().get_chapter("Kejadian", "1", "tb").await.unwrap();
+    assert_eq!(chapter.version.as_deref(), Some("Alkitab Terjemahan Baru (TB)"));
+    assert_eq!(chapter.title, vec![
+        "Allah menciptakan langit dan bumi serta isinya (1-4)",
+        "Hari pertama (5-5)",
+    ]);
+    assert_eq!(chapter.total_verses, 5);
+    assert_eq!(chapter.verses[0].verse, 1);
+    assert_eq!(chapter.verses[0].content, "Pada mulanya Allah menciptakan langit dan bumi.");
+    assert_eq!(chapter.verses[2].content, "Berfirmanlah Allah: \"Jadilah terang.\" Lalu terang itu jadi.");
+}
+
+#[tokio::test]
+async fn test_get_chapter_ayt_has_no_titles() {
+    let chapter = fixture_service().get_chapter("Kejadian", "1", "ayt").await.unwrap();
+    assert_eq!(chapter.version.as_deref(), Some("Alkitab Yang Terbuka (AYT)"));
+    // Section titles are only read for TB
+    assert!(chapter.title.is_empty());
+    assert_eq!(chapter.verses.iter().map(|v| v.verse).collect::<Vec<_>>(), vec![1, 2, 3]);
+    assert_eq!(chapter.verses[0].content, "Pada mulanya, Allah menciptakan langit dan bumi.");
+}
+
+#[tokio::test]
+async fn test_get_chapter_kjv_strips_strongs_numbers() {
+    let chapter = fixture_service().get_chapter("Genesis", "1", "kjv").await.unwrap();
+    assert_eq!(chapter.version.as_deref(), Some("King James Version"));
+    assert_eq!(chapter.verses[0].content, "In the beginning God created the heaven and the earth.");
+    assert_eq!(chapter.verses[1].content, "And the earth was without form, and void; and darkness was upon the face of the deep.");
+    assert_eq!(chapter.verses[2].content, "And God said, Let there be light: and there was light.");
+}
+
+#[test]
+fn test_fixture_paths_follow_the_url() {
+    let dir = std::path::Path::new("fixtures");
+    assert_eq!(fixture_path(dir, "https://alkitab.mobi/tb/Kej/1").unwrap(), dir.join("tb/Kej/1.html"));
+    assert_eq!(fixture_path(dir, "https://alkitab.mobi/tb/kej").unwrap(), dir.join("tb/kej.html"));
+    assert_eq!(fixture_path(dir, "https://alkitab.mobi/tb/../../etc").unwrap(), dir.join("etc.html"));
+}
+
 #[tokio::test]
 async fn test_format_book_name() {
-    let service = BibleService::new();
+    let service = fixture_service();
     let result = service.format_book_name("genesis");
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), "Genesis");
@@ -36,8 +83,8 @@ async fn test_format_book_name() {
 
 #[tokio::test]
 async fn test_invalid_book_name() {
-    let service = BibleService::new();
-    let result = service.find_book_metadata("InvalidBook").await;
+    let service = fixture_service();
+    let result = service.find_book_metadata("InvalidBook", "tb");
     assert!(result.is_err());
     if let Err(AppError::InvalidBookError(_)) = result {
         // Expected error type
@@ -48,7 +95,7 @@ async fn test_invalid_book_name() {
 
 #[tokio::test]
 async fn test_invalid_chapter() {
-    let service = BibleService::new();
+    let service = fixture_service();
     // Try with a very large chapter number that's unlikely to exist
     let result = service.get_chapter("Genesis", "9999", "tb").await;
     match result {
@@ -66,4 +113,57 @@ async fn test_invalid_chapter() {
             }
         }
     }
-} 
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_find_book_metadata_follows_the_version() {
+    let service = fixture_service();
+    let tb = service.book_metadata("3 Yohanes", "tb").unwrap();
+    let kjv = service.book_metadata("3 John", "kjv").unwrap();
+    assert_eq!(tb.total_verses, 15);
+    assert_eq!(kjv.total_verses, 14);
+    // The TB numbers Psalm superscriptions as verses
+    assert_eq!(service.book_metadata("Mazmur", "").unwrap().total_verses, 2527);
+    assert_eq!(service.book_metadata("Mazmur", "kjv").unwrap().total_verses, 2461);
+}
+
+#[tokio::test]
+async fn test_book_metadata_counts_verses_per_chapter() {
+    let metadata = fixture_service().book_metadata("Genesis", "tb").unwrap();
+    assert_eq!(metadata.book, "Kejadian");
+    assert_eq!(metadata.english_name, "Genesis");
+    assert_eq!(metadata.testament, Testament::Old);
+    assert_eq!(metadata.order, 1);
+    assert_eq!(metadata.total_chapters, 50);
+    assert_eq!(metadata.total_verses, 1533);
+    assert_eq!(metadata.chapters[0], ChapterMetadata {
+        chapter: 1,
+        verses: 31,
+        testament: Testament::Old,
+        order: 1,
+        book: "Kejadian".to_string(),
+        english_name: "Genesis".to_string(),
+    });
+
+    let matius = fixture_service().book_metadata("Matius", "tb").unwrap();
+    assert_eq!((matius.testament, matius.order), (Testament::New, 40));
+
+    // The legacy shape counts chapters
+    let legacy = BibleMetadata::from(&metadata);
+    assert_eq!(legacy.total_verse, 50);
+    assert_eq!(legacy.verses, (1..=50).collect::<Vec<i32>>());
+}
+
+#[tokio::test]
+async fn test_impossible_references_are_rejected_before_fetching() {
+    // No fixtures exist for these pages, so a fetch would fail with a different message
+    match fixture_service().get_chapter("Kejadian", "51", "tb").await {
+        Err(AppError::NotFound(msg)) => assert_eq!(msg, "Chapter 51 not found in Kejadian, which has 50 chapters"),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+    let selection = VerseSelection::parse("30-32").unwrap();
+    match fixture_service().get_verses("Kejadian", "2", &selection, "tb").await {
+        Err(AppError::NotFound(msg)) => assert_eq!(msg, "Verse 30 not found in Kejadian 2"),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use alkitab_api_rust::models::bible::Testament;
+use alkitab_api_rust::models::book::{Book, BookError, BOOKS};
+
+#[test]
+fn test_registry_identifiers_are_unique() {
+    assert_eq!(BOOKS.len(), 66);
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for book in BOOKS.iter() {
+        assert_eq!(Book::from_usfm(book.usfm), Some(book));
+        assert_eq!(Book::from_osis(book.osis), Some(book));
+        assert_eq!(Book::from_indonesian(book.indonesian), Some(book));
+
+        let names = [book.indonesian, book.english, book.path_segment].into_iter().chain(book.aliases.iter().copied());
+        for name in names {
+            let key = name.to_lowercase().replace(' ', "");
+            if let Some(other) = seen.insert(key, book.indonesian) {
+                assert_eq!(other, book.indonesian, "'{}' names both {} and {}", name, other, book.indonesian);
+            }
+            assert_eq!(Book::lookup(name), Some(book), "'{}' does not resolve to {}", name, book.indonesian);
+        }
+    }
+}
+
+#[test]
+fn test_short_names_and_aliases_resolve_to_the_same_book() {
+    let ezra = Book::resolve("Eza").unwrap();
+    assert_eq!(Book::resolve("ezr").unwrap(), ezra);
+    assert_eq!(ezra.path_segment, "Eza");
+
+    let ayub = Book::resolve("ayb").unwrap();
+    assert_eq!(ayub, Book::resolve("Ayu").unwrap());
+    assert_eq!(ayub, Book::resolve("Job").unwrap());
+    assert_eq!(ayub.path_segment, "Ayu");
+
+    assert_eq!(Book::resolve("why").unwrap().indonesian, "Wahyu");
+    assert_eq!(Book::resolve("mzm").unwrap().path_segment, "Maz");
+}
+
+#[test]
+fn test_resolve_accepts_names_prefixes_and_codes() {
+    assert_eq!(Book::resolve("kidung agung").unwrap().usfm, "SNG");
+    assert_eq!(Book::resolve("1 kor.").unwrap().indonesian, "1 Korintus");
+    assert_eq!(Book::resolve("Revel").unwrap().indonesian, "Wahyu");
+    assert_eq!(Book::from_usfm("1co").unwrap().osis, "1Cor");
+    assert_eq!(Book::from_osis("Ps").unwrap().indonesian, "Mazmur");
+
+    assert_eq!(Book::resolve(" "), Err(BookError::Missing));
+    assert_eq!(Book::resolve("Nothing"), Err(BookError::Unknown("Nothing".to_string())));
+    assert_eq!(Book::resolve("Fil").unwrap_err().to_string(), "ambiguous book 'Fil' (could be Filemon, Filipi)");
+}
+
+#[test]
+fn test_order_and_testament_follow_the_registry() {
+    let matius = Book::from_indonesian("Matius").unwrap();
+    assert_eq!(matius.index(), 39);
+    assert_eq!(matius.testament(), Testament::New);
+    assert_eq!(BOOKS[38].testament(), Testament::Old);
+    assert_eq!(BOOKS[18].chapter_count(), 150);
+}
@@ -0,0 +1,39 @@
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::models::bible::Testament;
+use alkitab_api_rust::models::books::catalogue;
+use alkitab_api_rust::services::bible::books;
+
+#[test]
+fn test_catalogue_lists_every_book_in_order() {
+    let all = catalogue(None);
+    assert_eq!(all.len(), 66);
+    assert_eq!(all.iter().map(|b| b.order).collect::<Vec<_>>(), (1..=66).collect::<Vec<_>>());
+    assert_eq!(all.iter().map(|b| b.chapters).sum::<i32>(), 1189);
+
+    let kejadian = &all[0];
+    assert_eq!(kejadian.book, "Kejadian");
+    assert_eq!(kejadian.english_name, "Genesis");
+    assert_eq!(kejadian.short_name, "Kej");
+    assert!(kejadian.aliases.is_empty());
+    assert_eq!(kejadian.testament, Testament::Old);
+    assert_eq!(kejadian.chapters, 50);
+
+    let mazmur = &all[18];
+    assert_eq!((mazmur.book.as_str(), mazmur.short_name.as_str()), ("Mazmur", "Maz"));
+    assert_eq!(mazmur.aliases, vec!["mzm"]);
+    assert_eq!(all[65].aliases, vec!["why"]);
+}
+
+#[test]
+fn test_catalogue_filters_by_testament() {
+    let new = catalogue(Some(Testament::New));
+    assert_eq!(new.len(), 27);
+    assert_eq!(new[0].book, "Matius");
+    assert!(new.iter().all(|b| b.testament == Testament::New));
+    assert_eq!(books(Some("OLD")).unwrap().len(), 39);
+
+    match books(Some("apocrypha")) {
+        Err(AppError::InvalidInput(msg)) => assert_eq!(msg, "Unknown testament 'apocrypha', expected old or new"),
+        other => panic!("Expected InvalidInput, got {:?}", other),
+    }
+}
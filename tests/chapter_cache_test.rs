@@ -0,0 +1,129 @@
+use std::sync::Arc;
+use std::time::Duration;
+use alkitab_api_rust::services::bible::BibleService;
+use alkitab_api_rust::services::cache::{CacheStatus, ChapterCache, ChapterKey};
+use alkitab_api_rust::error::AppError;
+
+mod common;
+
+use common::{chapter, StubSource};
+
+/// Chapters past 50 are missing; fetches stay in flight long enough for concurrent callers to pile up
+fn source() -> Arc<StubSource> {
+    Arc::new(StubSource::new(&["tb", "ayt"]).with_chapters(50).with_delay(Duration::from_millis(20)))
+}
+
+#[tokio::test]
+async fn test_service_serves_repeated_chapters_from_cache() {
+    let source = source();
+    let cache = Arc::new(ChapterCache::new(Duration::from_secs(60), 10));
+    let service = BibleService::with_source(source.clone()).with_cache(cache.clone());
+
+    service.get_chapter("Yohanes", "3", "tb").await.unwrap();
+    // Different spellings of the same book share one cache entry
+    service.get_chapter("yoh", "3", "tb").await.unwrap();
+    service.get_chapter("John", "3", "TB").await.unwrap();
+    service.get_chapter("Yohanes", "3", "ayt").await.unwrap();
+
+    assert_eq!(source.fetch_count(), 2);
+    let stats = cache.stats();
+    assert_eq!((stats.hits, stats.misses, stats.entries), (2, 2, 2));
+}
+
+#[tokio::test]
+async fn test_concurrent_requests_share_one_fetch() {
+    let source = source();
+    let cache = Arc::new(ChapterCache::new(Duration::from_secs(60), 10));
+    let service = BibleService::with_source(source.clone()).with_cache(cache);
+
+    let results = futures::future::join_all((0..20).map(|_| service.get_chapter("Mazmur", "23", "tb"))).await;
+    assert!(results.iter().all(|r| r.as_ref().is_ok_and(|c| c.chapter == 23)));
+    assert_eq!(source.fetch_count(), 1);
+}
+
+#[tokio::test]
+async fn test_concurrent_requests_share_one_error() {
+    let source = source();
+    // Coalescing still applies with caching disabled
+    let cache = Arc::new(ChapterCache::new(Duration::ZERO, 0));
+    let service = BibleService::with_source(source.clone()).with_cache(cache);
+
+    let results = futures::future::join_all((0..5).map(|_| service.get_chapter("Mazmur", "119", "tb"))).await;
+    for result in &results {
+        match result {
+            Err(AppError::NotFound(msg)) => assert_eq!(msg, "Mazmur 119 not found"),
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
+    }
+    assert_eq!(source.fetch_count(), 1);
+
+    // Errors are not remembered once the fetch has finished
+    assert!(service.get_chapter("Mazmur", "119", "tb").await.is_err());
+    assert_eq!(source.fetch_count(), 2);
+}
+
+#[test]
+fn test_cache_marks_entries_stale_after_ttl() {
+    let cache = ChapterCache::new(Duration::from_millis(20), 10);
+    let key = ChapterKey::new("tb", "yoh", 3);
+    cache.insert(key.clone(), chapter("Yohanes", 3, []));
+    assert!(matches!(cache.lookup(&key), Some((_, CacheStatus::Hit))));
+
+    std::thread::sleep(Duration::from_millis(30));
+    // Expired chapters are kept so they can still be served stale
+    assert!(matches!(cache.lookup(&key), Some((_, CacheStatus::Stale))));
+    assert_eq!(cache.stats().entries, 1);
+    assert_eq!(cache.stats().stale_hits, 1);
+}
+
+#[tokio::test]
+async fn test_stale_chapter_is_served_and_refreshed_in_background() {
+    let source = source();
+    let cache = Arc::new(ChapterCache::new(Duration::from_millis(100), 10));
+    let service = BibleService::with_source(source.clone()).with_cache(cache);
+
+    let (_, status) = service.get_chapter_with_status("Kejadian", "1", "tb").await.unwrap();
+    assert_eq!(status, CacheStatus::Miss);
+    tokio::time::sleep(Duration::from_millis(110)).await;
+
+    // The upstream is down: the expired copy is served and the refresh fails quietly
+    source.set_down(true);
+    let (stale, status) = service.get_chapter_with_status("Kejadian", "1", "tb").await.unwrap();
+    assert_eq!(status, CacheStatus::Stale);
+    assert_eq!(stale.chapter, 1);
+    tokio::time::sleep(Duration::from_millis(40)).await;
+    assert_eq!(source.fetch_count(), 2);
+
+    // Once the upstream recovers the background refresh replaces the stale copy
+    source.set_down(false);
+    let (_, status) = service.get_chapter_with_status("Kejadian", "1", "tb").await.unwrap();
+    assert_eq!(status, CacheStatus::Stale);
+    tokio::time::sleep(Duration::from_millis(40)).await;
+    let (_, status) = service.get_chapter_with_status("Kejadian", "1", "tb").await.unwrap();
+    assert_eq!(status, CacheStatus::Hit);
+    assert_eq!(source.fetch_count(), 3);
+}
+
+#[test]
+fn test_cache_evicts_least_recently_used() {
+    let cache = ChapterCache::new(Duration::from_secs(60), 2);
+    let john = ChapterKey::new("tb", "yoh", 3);
+    let psalm = ChapterKey::new("tb", "mzm", 23);
+    let genesis = ChapterKey::new("tb", "kej", 1);
+
+    cache.insert(john.clone(), chapter("Yohanes", 3, []));
+    std::thread::sleep(Duration::from_millis(2));
+    cache.insert(psalm.clone(), chapter("Mazmur", 23, []));
+    std::thread::sleep(Duration::from_millis(2));
+    cache.lookup(&john);
+    cache.insert(genesis.clone(), chapter("Kejadian", 1, []));
+
+    assert_eq!(cache.stats().entries, 2);
+    assert!(cache.lookup(&john).is_some());
+    assert!(cache.lookup(&psalm).is_none());
+    assert!(cache.lookup(&genesis).is_some());
+
+    let disabled = ChapterCache::new(Duration::ZERO, 10);
+    disabled.insert(john.clone(), chapter("Yohanes", 3, []));
+    assert!(disabled.lookup(&john).is_none());
+}
@@ -0,0 +1,128 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use actix_web::ResponseError;
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::models::bible::CircuitState;
+use alkitab_api_rust::services::bible::BibleService;
+use alkitab_api_rust::services::circuit_breaker::{CircuitBreaker, CircuitBreakerSource};
+use alkitab_api_rust::services::disk_cache::DiskCachedSource;
+use alkitab_api_rust::services::local_corpus::LocalCorpusSource;
+use alkitab_api_rust::services::source::{ScriptureSource, VersionRoutedSource};
+
+mod common;
+
+use common::{chapter, StubSource};
+
+fn service(source: Arc<StubSource>, breaker: Arc<CircuitBreaker>) -> BibleService {
+    BibleService::with_source(Arc::new(CircuitBreakerSource::new(source, breaker)))
+}
+
+#[tokio::test]
+async fn test_circuit_opens_after_consecutive_failures_and_fails_fast() {
+    let source = Arc::new(StubSource::new(&["tb"]).with_chapters(50));
+    let breaker = Arc::new(CircuitBreaker::new(3, Duration::from_secs(30)));
+    let service = service(source.clone(), breaker.clone());
+
+    source.set_down(true);
+    for _ in 0..3 {
+        assert!(matches!(service.get_chapter("Kejadian", "1", "tb").await, Err(AppError::ExternalService(_))));
+    }
+    assert_eq!(breaker.status().state, CircuitState::Open);
+
+    match service.get_chapter("Kejadian", "1", "tb").await {
+        Err(AppError::ServiceUnavailable { message, retry_after_secs }) => {
+            assert!(message.contains("3 consecutive failures"), "{}", message);
+            assert!((1..=30).contains(&retry_after_secs));
+        }
+        other => panic!("Expected ServiceUnavailable, got {:?}", other),
+    }
+    // The open circuit did not reach the upstream
+    assert_eq!(source.fetch_count(), 3);
+}
+
+#[tokio::test]
+async fn test_half_open_probe_closes_or_reopens_the_circuit() {
+    let source = Arc::new(StubSource::new(&["tb"]).with_chapters(50));
+    let breaker = Arc::new(CircuitBreaker::new(1, Duration::from_millis(50)));
+    let service = service(source.clone(), breaker.clone());
+
+    source.set_down(true);
+    assert!(service.get_chapter("Kejadian", "1", "tb").await.is_err());
+    assert_eq!(breaker.status().state, CircuitState::Open);
+
+    // A failed probe opens the circuit for another cool-down
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    assert_eq!(breaker.status().state, CircuitState::HalfOpen);
+    assert!(matches!(service.get_chapter("Kejadian", "1", "tb").await, Err(AppError::ExternalService(_))));
+    assert_eq!(breaker.status().state, CircuitState::Open);
+    assert!(matches!(service.get_chapter("Kejadian", "1", "tb").await, Err(AppError::ServiceUnavailable { .. })));
+    assert_eq!(source.fetch_count(), 2);
+
+    // A successful probe closes it again
+    source.set_down(false);
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    assert!(service.get_chapter("Kejadian", "1", "tb").await.is_ok());
+    let status = breaker.status();
+    assert_eq!(status.state, CircuitState::Closed);
+    assert_eq!(status.consecutive_failures, 0);
+    assert!(status.last_error.is_none());
+}
+
+#[tokio::test]
+async fn test_only_upstream_errors_count_as_failures() {
+    let source = Arc::new(StubSource::new(&["tb"]).with_chapters(50));
+    let breaker = Arc::new(CircuitBreaker::new(2, Duration::from_secs(30)));
+    let service = service(source.clone(), breaker.clone());
+
+    for _ in 0..5 {
+        assert!(matches!(service.get_chapter("Mazmur", "99", "tb").await, Err(AppError::NotFound(_))));
+    }
+    assert_eq!(breaker.status().state, CircuitState::Closed);
+    assert_eq!(breaker.status().consecutive_failures, 0);
+}
+
+#[tokio::test]
+async fn test_local_and_disk_cached_chapters_bypass_the_circuit() {
+    let upstream = Arc::new(StubSource::new(&["tb"]).with_chapters(50));
+    let breaker = Arc::new(CircuitBreaker::new(3, Duration::from_secs(30)));
+    let dir = std::env::temp_dir().join(format!("alkitab-breaker-disk-cache-{}", std::process::id()));
+    let remote: Arc<dyn ScriptureSource> = Arc::new(
+        DiskCachedSource::new(Arc::new(CircuitBreakerSource::new(upstream.clone(), breaker.clone())), &dir).unwrap()
+    );
+    let local: Arc<dyn ScriptureSource> = Arc::new(LocalCorpusSource::new("kjv", vec![chapter("Rut", 1, 1..=22)]).unwrap());
+    let service = BibleService::with_source(Arc::new(VersionRoutedSource::new(remote, HashMap::from([("kjv".to_string(), local)]))));
+    service.get_chapter("Kejadian", "1", "tb").await.unwrap();
+
+    // Successes that never reach the upstream do not reset the failure count
+    upstream.set_down(true);
+    for _ in 0..2 {
+        assert!(service.get_chapter("Keluaran", "1", "tb").await.is_err());
+    }
+    assert!(service.get_chapter("Kejadian", "1", "tb").await.is_ok());
+    assert!(service.get_chapter("Rut", "1", "kjv").await.is_ok());
+    assert_eq!(breaker.status().consecutive_failures, 2);
+    assert!(service.get_chapter("Keluaran", "1", "tb").await.is_err());
+    assert_eq!(breaker.status().state, CircuitState::Open);
+
+    // While the circuit is open they are still served
+    assert!(service.get_chapter("Kejadian", "1", "tb").await.is_ok());
+    assert!(service.get_chapter("Rut", "1", "kjv").await.is_ok());
+    assert!(matches!(service.get_chapter("Keluaran", "1", "tb").await, Err(AppError::ServiceUnavailable { .. })));
+    assert_eq!(breaker.status().state, CircuitState::Open);
+    assert_eq!(breaker.status().consecutive_failures, 3);
+    assert_eq!(upstream.fetch_count(), 4);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_service_unavailable_response_carries_retry_after() {
+    let error = AppError::ServiceUnavailable {
+        message: "Upstream unavailable".to_string(),
+        retry_after_secs: 12,
+    };
+    let response = error.error_response();
+    assert_eq!(response.status().as_u16(), 503);
+    assert_eq!(response.headers().get("Retry-After").unwrap(), "12");
+}
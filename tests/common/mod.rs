@@ -0,0 +1,193 @@
+//! Helpers shared by the integration tests
+#![allow(dead_code)]
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use futures::future::BoxFuture;
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::models::bible::{BibleChapter, Verse};
+use alkitab_api_rust::models::versification::verse_count;
+use alkitab_api_rust::services::alkitab_mobi::AlkitabMobiSource;
+use alkitab_api_rust::services::bible::BibleService;
+use alkitab_api_rust::services::fetcher::fixture_fetcher;
+use alkitab_api_rust::services::retry::RetryPolicy;
+use alkitab_api_rust::services::source::ScriptureSource;
+use alkitab_api_rust::services::validation::ChapterValidator;
+
+pub const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/alkitab_mobi");
+
+/// Scrapes the recorded alkitab.mobi pages, checking them with `validator`; run with
+/// ALKITAB_RECORD_FIXTURES=1 to re-record them
+pub fn fixture_source(validator: Option<ChapterValidator>) -> AlkitabMobiSource {
+    AlkitabMobiSource::new("https://alkitab.mobi")
+        .with_fetcher(fixture_fetcher(FIXTURES))
+        .with_retry(RetryPolicy::none())
+        .with_validator(validator)
+}
+
+/// Serves the recorded alkitab.mobi pages. Validation is off because most fixtures are
+/// excerpts of their chapters.
+pub fn fixture_service() -> BibleService {
+    BibleService::with_source(Arc::new(fixture_source(None)))
+}
+
+/// Untitled chapter whose verses read "Ayat <verse>"
+pub fn chapter(book: &str, chapter: i32, verses: impl IntoIterator<Item = i32>) -> BibleChapter {
+    let verses: Vec<Verse> = verses.into_iter()
+        .map(|verse| Verse { verse, content: format!("Ayat {}", verse) })
+        .collect();
+    BibleChapter {
+        book: vec![book.to_string()],
+        chapter,
+        title: Vec::new(),
+        total_verses: verses.len(),
+        version: None,
+        verses,
+    }
+}
+
+/// How many verses the chapters of a [`StubSource`] have
+pub enum StubVerses {
+    /// The same number in every chapter
+    Fixed(i32),
+    /// As many as the version's numbering gives the chapter
+    Versified,
+}
+
+/// In-memory source serving placeholder verses whose content is "<version> <verse>".
+///
+/// Every chapter exists unless configured otherwise. Fetches are counted once the
+/// request is polled, and every fetch fails like an unreachable upstream while the
+/// source is set down.
+pub struct StubSource {
+    fetches: AtomicUsize,
+    in_flight: AtomicUsize,
+    peak_in_flight: AtomicUsize,
+    down: AtomicBool,
+    versions: Vec<String>,
+    verses: StubVerses,
+    last_chapter: Option<i32>,
+    missing_chapters: Vec<(String, i32)>,
+    versions_down: Vec<String>,
+    missing_verses: Vec<(String, i32)>,
+    delay: Duration,
+}
+
+impl StubSource {
+    /// Serves the given versions with one verse per chapter
+    pub fn new(versions: &[&str]) -> Self {
+        StubSource {
+            fetches: AtomicUsize::new(0),
+            in_flight: AtomicUsize::new(0),
+            peak_in_flight: AtomicUsize::new(0),
+            down: AtomicBool::new(false),
+            versions: versions.iter().map(|v| v.to_string()).collect(),
+            verses: StubVerses::Fixed(1),
+            last_chapter: None,
+            missing_chapters: Vec::new(),
+            versions_down: Vec::new(),
+            missing_verses: Vec::new(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn with_verses(mut self, verses: StubVerses) -> Self {
+        self.verses = verses;
+        self
+    }
+
+    /// Chapters past `last` are not found, whatever the book
+    pub fn with_chapters(mut self, last: i32) -> Self {
+        self.last_chapter = Some(last);
+        self
+    }
+
+    pub fn without_chapter(mut self, book: &str, chapter: i32) -> Self {
+        self.missing_chapters.push((book.to_string(), chapter));
+        self
+    }
+
+    /// Leaves a verse out of every chapter of a version
+    pub fn without_verse(mut self, version: &str, verse: i32) -> Self {
+        self.missing_verses.push((version.to_string(), verse));
+        self
+    }
+
+    /// Fails every fetch of a version like an unreachable upstream
+    pub fn with_version_down(mut self, version: &str) -> Self {
+        self.versions_down.push(version.to_string());
+        self
+    }
+
+    /// Keeps every fetch in flight for `delay`, so concurrent callers can pile up
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn fetch_count(&self) -> usize {
+        self.fetches.load(Ordering::SeqCst)
+    }
+
+    /// Most fetches that were in flight at the same time
+    pub fn peak_in_flight(&self) -> usize {
+        self.peak_in_flight.load(Ordering::SeqCst)
+    }
+
+    pub fn set_down(&self, down: bool) {
+        self.down.store(down, Ordering::SeqCst);
+    }
+
+    fn chapter(&self, book: &str, chapter: i32, version: &str) -> Result<BibleChapter, AppError> {
+        if self.down.load(Ordering::SeqCst) || self.versions_down.iter().any(|v| v == version) {
+            return Err(AppError::ExternalService("Failed to fetch chapter: HTTP 502 Bad Gateway".to_string()));
+        }
+        let not_found = || AppError::NotFound(format!("{} {} not found", book, chapter));
+        if self.last_chapter.is_some_and(|last| chapter > last)
+            || self.missing_chapters.iter().any(|(b, c)| b == book && *c == chapter)
+        {
+            return Err(not_found());
+        }
+        let count = match self.verses {
+            StubVerses::Fixed(count) => count,
+            StubVerses::Versified => verse_count(book, chapter, version).ok_or_else(not_found)?,
+        };
+        let verses: Vec<Verse> = (1..=count)
+            .filter(|verse| !self.missing_verses.iter().any(|(v, n)| v == version && n == verse))
+            .map(|verse| Verse { verse, content: format!("{} {}", version, verse) })
+            .collect();
+        Ok(BibleChapter {
+            book: vec![book.to_string()],
+            chapter,
+            title: vec![format!("{} {}", book, chapter)],
+            total_verses: verses.len(),
+            version: Some(version.to_string()),
+            verses,
+        })
+    }
+}
+
+impl ScriptureSource for StubSource {
+    fn name(&self) -> &'static str {
+        "stub"
+    }
+
+    fn fetch_chapter<'a>(&'a self, book: &'a str, chapter: i32, version: &'a str) -> BoxFuture<'a, Result<BibleChapter, AppError>> {
+        Box::pin(async move {
+            // Counted once polled: an open circuit drops the request unpolled
+            self.fetches.fetch_add(1, Ordering::SeqCst);
+            let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+            if !self.delay.is_zero() {
+                tokio::time::sleep(self.delay).await;
+            }
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.chapter(book, chapter, version)
+        })
+    }
+
+    fn list_versions(&self) -> Vec<String> {
+        self.versions.clone()
+    }
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::services::bible::BibleService;
+
+mod common;
+
+use common::{StubSource, StubVerses};
+
+/// Numbers verses like each version's versification; AYT leaves out verse 2 and ESV is down
+fn service() -> BibleService {
+    let source = StubSource::new(&["tb", "ayt", "kjv", "esv"])
+        .with_verses(StubVerses::Versified)
+        .without_verse("ayt", 2)
+        .with_version_down("esv");
+    BibleService::with_source(Arc::new(source))
+}
+
+fn versions(codes: &[&str]) -> Vec<String> {
+    codes.iter().map(|code| code.to_string()).collect()
+}
+
+#[tokio::test]
+async fn test_compare_aligns_verses_and_reports_failed_versions() {
+    let service = service();
+    let comparison = service.compare("3 John", "1", &versions(&["tb", "ayt", "kjv", "esv"])).await.unwrap();
+
+    assert_eq!(comparison.book, "3 Yohanes");
+    assert_eq!(comparison.chapter, 1);
+    assert_eq!(comparison.versions, versions(&["tb", "ayt", "kjv", "esv"]));
+    assert_eq!(comparison.verses.len(), 15);
+
+    let text = |s: &str| Some(s.to_string());
+    assert_eq!(comparison.verses[0].texts, vec![text("tb 1"), text("ayt 1"), text("kjv 1"), None]);
+    // A verse missing from one version is null in its column
+    assert_eq!(comparison.verses[1].texts, vec![text("tb 2"), None, text("kjv 2"), None]);
+    // The KJV's last verse covers both of the TB's
+    assert_eq!(comparison.verses[13].texts, vec![text("tb 14"), text("ayt 14"), text("kjv 14"), None]);
+    assert_eq!(comparison.verses[14].texts, vec![text("tb 15"), text("ayt 15"), text("kjv 14"), None]);
+
+    assert_eq!(comparison.errors.len(), 1);
+    assert_eq!(comparison.errors[0].version, "esv");
+    assert!(comparison.errors[0].error.contains("HTTP 502"), "{}", comparison.errors[0].error);
+}
+
+#[tokio::test]
+async fn test_compare_moves_psalm_verses_to_tb_numbers() {
+    let service = service();
+    let comparison = service.compare("Mazmur", "51", &versions(&["tb", "kjv"])).await.unwrap();
+    assert_eq!(comparison.verses.len(), 21);
+    // The superscription has no KJV verse
+    assert_eq!(comparison.verses[0].texts, vec![Some("tb 1".to_string()), None]);
+    assert_eq!(comparison.verses[2].texts, vec![Some("tb 3".to_string()), Some("kjv 1".to_string())]);
+}
+
+#[tokio::test]
+async fn test_compare_rejects_a_missing_chapter() {
+    let service = service();
+    match service.compare("Yudas", "2", &versions(&["tb", "kjv"])).await {
+        Err(AppError::NotFound(msg)) => assert_eq!(msg, "Chapter 2 not found in Yudas, which has 1 chapter"),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
@@ -0,0 +1,58 @@
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::import::csv::parse_csv;
+use alkitab_api_rust::import::{load_corpus, CorpusFormat, ImportErrorKind};
+
+#[test]
+fn test_parse_csv_rows() {
+    let input = "book,chapter,verse,text\n\
+        John,3,16,\"For God so loved the world, that he gave his only begotten Son\"\n\
+        Kej,1,1,Pada mulanya Allah menciptakan langit dan bumi.\n\
+        John,3,17,For God sent not his Son into the world to condemn the world, but that the world through him might be saved.\n";
+    let chapters = parse_csv(input, "mixed.csv", Some("Mixed")).unwrap();
+
+    assert_eq!(chapters.len(), 2);
+    assert_eq!(chapters[0].book, vec!["Yohanes"]);
+    assert_eq!(chapters[0].total_verses, 2);
+    assert_eq!(chapters[0].verses[0].content, "For God so loved the world, that he gave his only begotten Son");
+    // Unquoted commas are kept as part of the text
+    assert_eq!(chapters[0].verses[1].content, "For God sent not his Son into the world to condemn the world, but that the world through him might be saved.");
+    assert_eq!(chapters[1].book, vec!["Kejadian"]);
+    assert_eq!(chapters[1].version.as_deref(), Some("Mixed"));
+}
+
+#[test]
+fn test_parse_csv_errors() {
+    let error = parse_csv("Xyz,1,1,text", "x.csv", None).unwrap_err();
+    assert_eq!(error.kind, ImportErrorKind::UnknownBook("Xyz".to_string()));
+
+    let error = parse_csv("Genesis,1,1,a\nGenesis,one,2,b", "gen.csv", None).unwrap_err();
+    assert_eq!(error.kind, ImportErrorKind::InvalidChapter("one".to_string()));
+    assert_eq!(error.line, 2);
+
+    let error = parse_csv("Genesis,1,1,a\nGenesis,1,2,b\nGenesis,1,1,c", "gen.csv", None).unwrap_err();
+    assert_eq!(error.to_string(), "gen.csv:3: duplicate verse Kejadian 1:1");
+
+    let error = parse_csv("Genesis,1", "gen.csv", None).unwrap_err();
+    assert!(matches!(error.kind, ImportErrorKind::Malformed(_)));
+}
+
+#[test]
+fn test_load_corpus_reports_every_invalid_file() {
+    let dir = std::env::temp_dir().join(format!("alkitab-csv-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.csv"), "Genesis,1,1,a\nGenesis,1,1,b").unwrap();
+    std::fs::write(dir.join("b.csv"), "Exodus,1,1,a").unwrap();
+    std::fs::write(dir.join("c.csv"), "Xyz,1,1,a").unwrap();
+
+    let result = load_corpus(&dir, CorpusFormat::Csv, None);
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    match result {
+        Err(AppError::InvalidInput(msg)) => {
+            assert!(msg.contains("2 files failed"), "{}", msg);
+            assert!(msg.contains("a.csv:2: duplicate verse Kejadian 1:1"), "{}", msg);
+            assert!(msg.contains("c.csv:1: unknown book 'Xyz'"), "{}", msg);
+        }
+        other => panic!("Expected InvalidInput, got {:?}", other.map(|c| c.len())),
+    }
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use alkitab_api_rust::services::disk_cache::{DiskCachedSource, DISK_CACHE_SCHEMA_VERSION};
+use alkitab_api_rust::services::source::ScriptureSource;
+
+mod common;
+
+use common::StubSource;
+
+fn cache_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("alkitab-disk-cache-{}-{}", name, std::process::id()))
+}
+
+#[tokio::test]
+async fn test_disk_cache_survives_restart() {
+    let dir = cache_dir("restart");
+    let first = Arc::new(StubSource::new(&["tb"]).with_chapters(50));
+    let source = DiskCachedSource::new(first.clone(), &dir).unwrap();
+    let fetched = source.fetch_chapter("Kejadian", 1, "tb").await.unwrap();
+    assert!(source.fetch_chapter("Kejadian", 99, "tb").await.is_err());
+    assert_eq!(first.fetch_count(), 2);
+
+    let file = dir.join(format!("v{}", DISK_CACHE_SCHEMA_VERSION)).join("tb").join("Kej").join("1.json");
+    assert!(file.is_file());
+
+    // A new process reads the chapter back without touching the upstream
+    let second = Arc::new(StubSource::new(&["tb"]).with_chapters(50));
+    let source = DiskCachedSource::new(second.clone(), &dir).unwrap();
+    assert_eq!(source.fetch_chapter("Kejadian", 1, "tb").await.unwrap(), fetched);
+    assert_eq!(second.fetch_count(), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[tokio::test]
+async fn test_disk_cache_ignores_other_schemas_and_corrupt_entries() {
+    let dir = cache_dir("schema");
+    let outdated = dir.join("v0").join("tb").join("kej");
+    std::fs::create_dir_all(&outdated).unwrap();
+    std::fs::write(outdated.join("1.json"), "{}").unwrap();
+
+    let corrupt = dir.join(format!("v{}", DISK_CACHE_SCHEMA_VERSION)).join("tb").join("Kel");
+    std::fs::create_dir_all(&corrupt).unwrap();
+    std::fs::write(corrupt.join("1.json"), "not json").unwrap();
+
+    let inner = Arc::new(StubSource::new(&["tb"]).with_chapters(50));
+    let source = DiskCachedSource::new(inner.clone(), &dir).unwrap();
+    // Other schema versions are ignored, not deleted
+    assert!(outdated.join("1.json").is_file());
+
+    source.fetch_chapter("Kejadian", 1, "tb").await.unwrap();
+    let chapter = source.fetch_chapter("Keluaran", 1, "tb").await.unwrap();
+    assert_eq!(chapter.book, vec!["Keluaran"]);
+    assert_eq!(inner.fetch_count(), 2);
+
+    // The corrupt entry was replaced
+    source.fetch_chapter("Keluaran", 1, "tb").await.unwrap();
+    assert_eq!(inner.fetch_count(), 2);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
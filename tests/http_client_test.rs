@@ -0,0 +1,96 @@
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread;
+use alkitab_api_rust::config::HttpClientConfig;
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::services::alkitab_mobi::AlkitabMobiSource;
+use alkitab_api_rust::services::http_client::{build_client, describe_error};
+use alkitab_api_rust::services::retry::RetryPolicy;
+use alkitab_api_rust::services::source::ScriptureSource;
+
+/// Serves one request with `body` and returns the raw request it received
+fn serve_once(body: &'static str) -> (String, thread::JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let handle = thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = vec![0; 4096];
+        let read = stream.read(&mut request).unwrap();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(), body
+        );
+        stream.write_all(response.as_bytes()).unwrap();
+        String::from_utf8_lossy(&request[..read]).to_string()
+    });
+    (address, handle)
+}
+
+#[tokio::test]
+async fn test_requests_use_the_configured_client() {
+    let (address, server) = serve_once(concat!(
+        r#"<p><span class="reftext"><a href="/tb/Kej/1/1/">1</a></span> "#,
+        r#"<span data-dur="4">Pada mulanya Allah menciptakan langit dan bumi.</span></p>"#,
+    ));
+    let client = build_client(&HttpClientConfig {
+        user_agent: "alkitab-test/1.0".to_string(),
+        ..HttpClientConfig::default()
+    }).unwrap();
+    let source = AlkitabMobiSource::new(address)
+        .with_client(client)
+        .with_retry(RetryPolicy::none())
+        .with_validator(None);
+
+    assert_eq!(source.fetch_chapter("Kejadian", 1, "tb").await.unwrap().total_verses, 1);
+    let request = server.join().unwrap().to_lowercase();
+    assert!(request.starts_with("get /tb/kej/1 "), "{}", request);
+    assert!(request.contains("user-agent: alkitab-test/1.0"), "{}", request);
+}
+
+#[tokio::test]
+async fn test_connection_errors_keep_their_cause() {
+    // Bind and drop a listener to get a local port nothing listens on
+    let address = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+    let source = AlkitabMobiSource::new(format!("http://{}", address)).with_retry(RetryPolicy::none());
+
+    match source.fetch_chapter("Kejadian", 1, "tb").await {
+        Err(AppError::ExternalService(msg)) => {
+            assert!(msg.starts_with("Failed to fetch chapter: error sending request"), "{}", msg);
+            // The underlying connect error is not swallowed by reqwest's summary
+            assert!(msg.to_lowercase().contains("connect"), "{}", msg);
+            assert!(msg.matches(": ").count() >= 3, "{}", msg);
+        }
+        other => panic!("Expected ExternalService, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_proxy_is_rejected() {
+    let result = build_client(&HttpClientConfig {
+        proxy: Some("not a url".to_string()),
+        ..HttpClientConfig::default()
+    });
+    assert!(matches!(result, Err(AppError::InvalidInput(msg)) if msg.starts_with("Invalid HTTP proxy not a url")));
+}
+
+#[test]
+fn test_describe_error_walks_the_cause_chain() {
+    let io = std::io::Error::new(std::io::ErrorKind::ConnectionRefused, "Connection refused");
+    let outer = std::io::Error::other(Wrapped(io));
+    assert_eq!(describe_error(&outer), "request failed: Connection refused");
+}
+
+#[derive(Debug)]
+struct Wrapped(std::io::Error);
+
+impl std::fmt::Display for Wrapped {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "request failed")
+    }
+}
+
+impl std::error::Error for Wrapped {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
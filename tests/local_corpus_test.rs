@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use alkitab_api_rust::models::bible::BibleChapter;
+use alkitab_api_rust::services::bible::BibleService;
+use alkitab_api_rust::services::local_corpus::LocalCorpusSource;
+use alkitab_api_rust::services::source::{ScriptureSource, VersionRoutedSource};
+use alkitab_api_rust::error::AppError;
+
+mod common;
+
+use common::chapter;
+
+fn write_corpus(name: &str, chapters: &[BibleChapter]) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("alkitab-{}-{}.json", name, std::process::id()));
+    std::fs::write(&path, serde_json::to_string(chapters).unwrap()).unwrap();
+    path
+}
+
+#[tokio::test]
+async fn test_local_corpus_serves_from_memory() {
+    let path = write_corpus("tb", &[chapter("Yohanes", 3, 1..=36), chapter("Yohanes", 1, 1..=51)]);
+    let source = LocalCorpusSource::load("tb", &path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let service = BibleService::with_source(Arc::new(source));
+    let result = service.get_chapter("John", "3", "tb").await.unwrap();
+    assert_eq!(result.book, vec!["Yohanes"]);
+    assert_eq!(result.total_verses, 36);
+
+    let metadata = service.find_book_metadata("Yohanes", "tb").unwrap();
+    assert_eq!(metadata.total_verse, 21);
+
+    match service.get_chapter("Yohanes", "2", "tb").await {
+        Err(AppError::NotFound(_)) => {}
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_local_corpus_rejects_duplicates_and_missing_files() {
+    let result = LocalCorpusSource::new("tb", vec![chapter("Rut", 1, 1..=22), chapter("Rut", 1, 1..=22)]);
+    assert!(matches!(result, Err(AppError::InvalidInput(_))));
+
+    let result = LocalCorpusSource::load("tb", "/nonexistent/corpus.json");
+    assert!(matches!(result, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_local_corpus_resolves_book_names() {
+    // Any spelling the registry accepts is stored under the Indonesian name
+    let source = LocalCorpusSource::new("kjv", vec![chapter("Genesis", 1, 1..=31), chapter("kel", 1, 1..=22)]).unwrap();
+    let service = BibleService::with_source(Arc::new(source));
+    let genesis = service.get_chapter("Kejadian", "1", "kjv").await.unwrap();
+    assert_eq!(genesis.book, vec!["Kejadian"]);
+    assert_eq!(service.get_chapter("Exodus", "1", "kjv").await.unwrap().book, vec!["Keluaran"]);
+
+    match LocalCorpusSource::new("kjv", vec![chapter("Genesys", 1, 1..=31)]) {
+        Err(AppError::InvalidInput(msg)) => assert_eq!(msg, "Chapter 1 in the kjv corpus: unknown book 'Genesys'"),
+        other => panic!("Expected InvalidInput, got {:?}", other.map(|_| ())),
+    }
+    // Two spellings of one book are still duplicates
+    let result = LocalCorpusSource::new("kjv", vec![chapter("Rut", 1, 1..=22), chapter("Ruth", 1, 1..=22)]);
+    assert!(matches!(result, Err(AppError::InvalidInput(_))));
+}
+
+#[tokio::test]
+async fn test_only_untitled_tb_chapters_get_a_default_title() {
+    let tb = LocalCorpusSource::new("tb", vec![chapter("Rut", 1, 1..=22)]).unwrap();
+    assert_eq!(tb.fetch_chapter("Rut", 1, "tb").await.unwrap().title, vec!["Rut 1"]);
+    let kjv = LocalCorpusSource::new("kjv", vec![chapter("Rut", 1, 1..=22)]).unwrap();
+    assert!(kjv.fetch_chapter("Rut", 1, "kjv").await.unwrap().title.is_empty());
+}
+
+#[tokio::test]
+async fn test_version_routed_source() {
+    let tb: Arc<dyn ScriptureSource> = Arc::new(LocalCorpusSource::new("tb", vec![chapter("Rut", 1, 1..=22)]).unwrap());
+    let kjv: Arc<dyn ScriptureSource> = Arc::new(LocalCorpusSource::new("kjv", vec![chapter("Rut", 1, 1..=21)]).unwrap());
+    let mut versions = HashMap::new();
+    versions.insert("kjv".to_string(), kjv);
+    let routed = VersionRoutedSource::new(tb, versions);
+
+    assert_eq!(routed.fetch_chapter("Rut", 1, "kjv").await.unwrap().total_verses, 21);
+    assert_eq!(routed.fetch_chapter("Rut", 1, "tb").await.unwrap().total_verses, 22);
+    assert_eq!(routed.list_versions(), vec!["kjv".to_string(), "tb".to_string()]);
+}
@@ -0,0 +1,76 @@
+use alkitab_api_rust::import::osis::{parse_osis, write_osis};
+use alkitab_api_rust::import::usfm::parse_usfm;
+use alkitab_api_rust::import::ImportErrorKind;
+
+const RUTH: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<osis xmlns="http://www.bibletechnologies.net/2003/OSIS/namespace">
+  <osisText osisIDWork="TB">
+    <header><work osisWork="TB"><title>Terjemahan Baru</title></work></header>
+    <div type="book" osisID="Ruth">
+      <title type="main">Rut</title>
+      <chapter osisID="Ruth.1">
+        <title type="section">Elimelekh dan keluarganya pindah ke Moab</title>
+        <p>
+          <verse osisID="Ruth.1.1">Pada zaman para hakim memerintah ada kelaparan di tanah Israel.<note type="study">Catatan kaki.</note></verse>
+          <verse osisID="Ruth.1.2">Nama orang itu ialah <w lemma="strong:H458">Elimelekh</w>, nama isterinya Naomi.</verse>
+        </p>
+        <lg><l><verse sID="Ruth.1.3"/>Kemudian matilah Elimelekh,</l><l>suami Naomi.<verse eID="Ruth.1.3"/></l></lg>
+        <title type="section">Rut ikut Naomi ke Betlehem</title>
+        <p><verse osisID="Ruth.1.4">Keduanya mengambil perempuan Moab &amp; Orpa.</verse></p>
+      </chapter>
+      <chapter osisID="Ruth.2">
+        <verse osisID="Ruth.2.1">Naomi mempunyai seorang sanak.</verse>
+      </chapter>
+    </div>
+  </osisText>
+</osis>
+"#;
+
+#[test]
+fn test_parse_osis_chapters_and_titles() {
+    let chapters = parse_osis(RUTH, "rut.xml", Some("Alkitab Terjemahan Baru (TB)")).unwrap();
+    assert_eq!(chapters.len(), 2);
+
+    let first = &chapters[0];
+    assert_eq!(first.book, vec!["Rut"]);
+    assert_eq!(first.total_verses, 4);
+    assert_eq!(first.title, vec![
+        "Elimelekh dan keluarganya pindah ke Moab (1-3)".to_string(),
+        "Rut ikut Naomi ke Betlehem (4-4)".to_string(),
+    ]);
+    assert_eq!(first.verses[0].content, "Pada zaman para hakim memerintah ada kelaparan di tanah Israel.");
+    assert_eq!(first.verses[1].content, "Nama orang itu ialah Elimelekh, nama isterinya Naomi.");
+    assert_eq!(first.verses[2].content, "Kemudian matilah Elimelekh, suami Naomi.");
+    assert_eq!(first.verses[3].content, "Keduanya mengambil perempuan Moab & Orpa.");
+    assert!(chapters[1].title.is_empty());
+}
+
+#[test]
+fn test_osis_round_trip() {
+    let usfm = "\\id RUT\n\\c 1\n\\s1 Elimelekh pindah ke Moab\n\\p\n\\v 1 Pada zaman para hakim.\n\\v 2 Nama orang itu <Elimelekh>.\n\\s1 Rut ikut Naomi\n\\p\n\\v 3 Keduanya.\n\\c 2\n\\p\n\\v 1 Naomi.";
+    let chapters = parse_usfm(usfm, "rut.usfm", Some("TB")).unwrap();
+
+    let document = write_osis(&chapters, "TB").unwrap();
+    assert!(document.contains(r#"<verse osisID="Ruth.1.2">Nama orang itu &lt;Elimelekh&gt;.</verse>"#), "{}", document);
+    // Untitled chapters get no section headings
+    assert!(!document.contains("Rut 2"), "{}", document);
+
+    let parsed = parse_osis(&document, "rut.xml", Some("TB")).unwrap();
+    assert_eq!(parsed, chapters);
+}
+
+#[test]
+fn test_parse_osis_errors() {
+    let error = parse_osis("<osis>\n<verse osisID=\"Xyz.1.1\">a</verse></osis>", "xyz.xml", None).unwrap_err();
+    assert_eq!(error.kind, ImportErrorKind::UnknownBook("Xyz".to_string()));
+    assert_eq!(error.line, 2);
+
+    let error = parse_osis("<osis><verse osisID=\"Gen.x.1\">a</verse></osis>", "gen.xml", None).unwrap_err();
+    assert_eq!(error.kind, ImportErrorKind::InvalidChapter("x".to_string()));
+
+    let error = parse_osis("<osis>\n<verse osisID=\"Gen.1.1\">a</verse>\n<verse osisID=\"Gen.1.1\">b</verse></osis>", "gen.xml", None).unwrap_err();
+    assert_eq!(error.to_string(), "gen.xml:3: duplicate verse Kejadian 1:1");
+
+    let error = parse_osis("<osis><verse osisID=\"Gen.1.1\">a</chapter></osis>", "gen.xml", None).unwrap_err();
+    assert!(matches!(error.kind, ImportErrorKind::Malformed(_)));
+}
@@ -0,0 +1,95 @@
+use std::path::PathBuf;
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::models::bible::BibleChapter;
+use alkitab_api_rust::models::versification::{chapter_count, verse_count};
+use alkitab_api_rust::services::source::ScriptureSource;
+use alkitab_api_rust::services::validation::{Anomaly, ChapterValidator};
+
+mod common;
+
+use common::{chapter, fixture_source};
+
+const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/golden");
+
+/// Set to rewrite the golden snapshots from the current parser output
+const UPDATE_GOLDEN_ENV: &str = "ALKITAB_UPDATE_GOLDEN";
+
+/// Psalm 134 with the given verse numbers
+fn psalm(numbers: &[i32]) -> BibleChapter {
+    chapter("Mazmur", 134, numbers.iter().copied())
+}
+
+#[tokio::test]
+async fn test_parse_matches_golden_snapshots() {
+    let source = fixture_source(None);
+    for (book, chapter, version) in [("Kejadian", 1, "tb"), ("Kejadian", 1, "ayt"), ("Kejadian", 1, "kjv"), ("Mazmur", 117, "tb")] {
+        let parsed = source.fetch_chapter(book, chapter, version).await.unwrap();
+        let path = PathBuf::from(GOLDEN).join(format!("{}_{}_{}.json", version, book, chapter));
+        if std::env::var(UPDATE_GOLDEN_ENV).is_ok() {
+            std::fs::write(&path, serde_json::to_string_pretty(&parsed).unwrap() + "\n").unwrap();
+        }
+        let golden: BibleChapter = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(parsed, golden, "{} {} {} no longer matches {}", version, book, chapter, path.display());
+    }
+}
+
+#[tokio::test]
+async fn test_complete_chapter_passes_validation() {
+    let chapter = fixture_source(Some(ChapterValidator::default())).fetch_chapter("Mazmur", 117, "tb").await.unwrap();
+    assert_eq!(chapter.total_verses, 2);
+}
+
+#[tokio::test]
+async fn test_changed_markup_is_reported_as_drift() {
+    match fixture_source(Some(ChapterValidator::default())).fetch_chapter("Mazmur", 134, "tb").await {
+        Err(AppError::ParserDrift(msg)) => assert_eq!(msg, "Mazmur 134 (tb): no verses found"),
+        other => panic!("Expected ParserDrift, got {:?}", other),
+    }
+
+    // Without validation the same page looks like a missing chapter
+    assert!(matches!(fixture_source(None).fetch_chapter("Mazmur", 134, "tb").await, Err(AppError::NotFound(_))));
+}
+
+#[tokio::test]
+async fn test_excerpts_are_reported_as_missing_verses() {
+    match fixture_source(Some(ChapterValidator::default())).fetch_chapter("Kejadian", 1, "tb").await {
+        Err(AppError::ParserDrift(msg)) => assert_eq!(msg, "Kejadian 1 (tb): found 5 of 31 expected verses"),
+        other => panic!("Expected ParserDrift, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_validator_flags_duplicates_gaps_and_empty_verses() {
+    let validator = ChapterValidator::new(0);
+    assert!(validator.validate("Mazmur", "tb", &psalm(&[1, 2, 3])).is_empty());
+
+    let mut broken = psalm(&[1, 3, 3, 2]);
+    broken.verses[0].content = " ".to_string();
+    assert_eq!(validator.validate("Mazmur", "tb", &broken), vec![
+        Anomaly::EmptyContent { verse: 1 },
+        Anomaly::DuplicateVerse { verse: 3 },
+        Anomaly::OutOfOrder { previous: 3, verse: 2 },
+    ]);
+
+    assert_eq!(validator.validate("Mazmur", "tb", &psalm(&[1, 2, 3, 4])), vec![
+        Anomaly::UnexpectedVerse { verse: 4, expected: 3 },
+    ]);
+    assert_eq!(validator.validate("Mazmur", "tb", &psalm(&[1, 2])), vec![
+        Anomaly::MissingVerses { found: 2, expected: 3 },
+    ]);
+    // Versions may omit a disputed verse or two
+    assert!(ChapterValidator::new(1).validate("Mazmur", "niv", &psalm(&[1, 3])).is_empty());
+}
+
+#[test]
+fn test_verse_counts() {
+    assert_eq!(chapter_count("Kejadian"), Some(50));
+    assert_eq!(chapter_count("Mazmur"), Some(150));
+    assert_eq!(chapter_count("Obaja"), Some(1));
+    assert_eq!(verse_count("Mazmur", 119, "tb"), Some(176));
+    assert_eq!(verse_count("Mazmur", 151, "tb"), None);
+    assert_eq!(verse_count("Kejadian", 0, "tb"), None);
+    assert_eq!(verse_count("3 Yohanes", 1, "kjv"), Some(14));
+    assert_eq!(verse_count("3 Yohanes", 1, "tb"), Some(15));
+    assert_eq!(verse_count("Unknown", 1, "tb"), None);
+}
@@ -0,0 +1,65 @@
+use std::sync::Arc;
+use std::time::Duration;
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::services::bible::BibleService;
+use alkitab_api_rust::services::disk_cache::DiskCachedSource;
+use alkitab_api_rust::services::prefetch::{self, prefetch, PrefetchOptions, PrefetchProgress, DEFAULT_PREFETCH_CONCURRENCY, DEFAULT_PREFETCH_DELAY_MS, MIN_PREFETCH_DELAY_MS};
+
+mod common;
+
+use common::StubSource;
+
+#[tokio::test]
+async fn test_prefetch_fills_cache_and_reports_failures() {
+    let source = Arc::new(StubSource::new(&["tb"]).without_chapter("Obaja", 1).without_chapter("Yudas", 1));
+    let dir = std::env::temp_dir().join(format!("alkitab-prefetch-{}", std::process::id()));
+    let service = BibleService::with_source(Arc::new(DiskCachedSource::new(source.clone(), &dir).unwrap()));
+    let options = PrefetchOptions {
+        version: "tb".to_string(),
+        concurrency: 8,
+        delay: Duration::ZERO,
+    };
+    let progress = PrefetchProgress::new("tb");
+
+    prefetch(&service, &options, &progress).await;
+
+    let report = progress.snapshot();
+    assert!(!report.running);
+    assert_eq!(report.books, 66);
+    // Chapters come from the versification table, not from the source
+    assert_eq!(report.total_chapters, 1189);
+    assert_eq!(report.fetched, 1187);
+    assert_eq!(report.failed, 2);
+    let mut failures: Vec<_> = report.failures.iter().map(|f| (f.book.as_str(), f.chapter)).collect();
+    failures.sort();
+    assert_eq!(failures, vec![("Obaja", 1), ("Yudas", 1)]);
+    assert_eq!(source.fetch_count(), 1189);
+
+    // Requests after the warm-up are served from the disk cache
+    service.get_chapter("Kejadian", "2", "tb").await.unwrap();
+    service.get_chapter("Wahyu", "22", "tb").await.unwrap();
+    assert_eq!(source.fetch_count(), 1189);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_prefetch_without_disk_cache_is_rejected() {
+    match prefetch::start(PrefetchOptions::from_request("tb", None, None)) {
+        Err(AppError::InvalidInput(msg)) => assert!(msg.contains("[bible.disk_cache]"), "{}", msg),
+        other => panic!("Expected InvalidInput, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_requested_prefetch_delay_has_a_floor() {
+    let options = PrefetchOptions::from_request("TB", None, None);
+    assert_eq!(options.version, "tb");
+    assert_eq!(options.concurrency, DEFAULT_PREFETCH_CONCURRENCY);
+    assert_eq!(options.delay, Duration::from_millis(DEFAULT_PREFETCH_DELAY_MS));
+
+    let options = PrefetchOptions::from_request("tb", Some(2), Some(0));
+    assert_eq!(options.delay, Duration::from_millis(MIN_PREFETCH_DELAY_MS));
+    let options = PrefetchOptions::from_request("tb", Some(2), Some(1000));
+    assert_eq!(options.delay, Duration::from_millis(1000));
+}
@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+use alkitab_api_rust::models::book::Book;
+use alkitab_api_rust::models::reference::{parse_references, ScriptureReference, SegmentVerses};
+use alkitab_api_rust::error::AppError;
+
+fn resolve_book(name: &str) -> Option<&'static str> {
+    Book::resolve(name).ok().map(|book| book.indonesian)
+}
+
+#[test]
+fn test_resolve_book_names() {
+    assert_eq!(resolve_book("Kej"), Some("Kejadian"));
+    assert_eq!(resolve_book("Gen"), Some("Kejadian"));
+    assert_eq!(resolve_book("1 Kor"), Some("1 Korintus"));
+    assert_eq!(resolve_book("Mazmur"), Some("Mazmur"));
+    assert_eq!(resolve_book("Mzm"), Some("Mazmur"));
+    assert_eq!(resolve_book("song of solomon"), Some("Kidung Agung"));
+    assert_eq!(resolve_book("Nothing"), None);
+    // "Fil" could be Filipi or Filemon
+    assert_eq!(resolve_book("Fil"), None);
+}
+
+#[test]
+fn test_parse_references() {
+    let references = parse_references("Yoh 3:16-18; Mzm 23").unwrap();
+    assert_eq!(references.len(), 2);
+    assert_eq!(references[0].to_string(), "Yohanes 3:16-18");
+    assert_eq!(references[1].to_string(), "Mazmur 23");
+    assert!(matches!(references[1], ScriptureReference::Chapter { verses: None, .. }));
+
+    let references = parse_references("Gen 1:1–3; 1 Kor 13; Mazmur 23:1,4").unwrap();
+    let formatted: Vec<String> = references.iter().map(|r| r.to_string()).collect();
+    assert_eq!(formatted, vec!["Kejadian 1:1-3", "1 Korintus 13", "Mazmur 23:1,4"]);
+}
+
+#[test]
+fn test_parse_references_continues_previous_book() {
+    let references = parse_references("Yoh 3:16; 4:1").unwrap();
+    assert!(matches!(references[1], ScriptureReference::Chapter { book: "Yohanes", chapter: 4, .. }));
+}
+
+#[test]
+fn test_parse_references_reports_bad_segment() {
+    match parse_references("Yoh 3:16; Xyz 4") {
+        Err(AppError::InvalidInput(msg)) => assert!(msg.contains("Xyz 4"), "{}", msg),
+        other => panic!("Expected InvalidInput, got {:?}", other),
+    }
+    match parse_references("Yoh 3:18-16") {
+        Err(AppError::InvalidInput(msg)) => assert!(msg.contains("Yoh 3:18-16"), "{}", msg),
+        other => panic!("Expected InvalidInput, got {:?}", other),
+    }
+    assert!(parse_references(" ; ").is_err());
+}
+
+#[test]
+fn test_parse_cross_chapter_span() {
+    let references = parse_references("Kejadian 1:26-2:3").unwrap();
+    assert_eq!(references[0].to_string(), "Kejadian 1:26-2:3");
+
+    let segments = references[0].segments(&HashMap::new()).unwrap();
+    assert_eq!(segments.len(), 2);
+    assert_eq!((segments[0].book, segments[0].chapter), ("Kejadian", 1));
+    assert_eq!(segments[0].verses, SegmentVerses::From(26));
+    assert_eq!((segments[1].book, segments[1].chapter), ("Kejadian", 2));
+    match &segments[1].verses {
+        SegmentVerses::Selection(selection) => assert_eq!(selection.to_string(), "1-3"),
+        other => panic!("Expected a verse selection, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_cross_book_span() {
+    let references = parse_references("Mal 4:5 – Mat 1:1").unwrap();
+    assert_eq!(references[0].to_string(), "Maleakhi 4:5 - Matius 1:1");
+    assert_eq!(references[0].books_needing_chapter_counts(), vec!["Maleakhi"]);
+
+    let mut chapter_counts = HashMap::new();
+    chapter_counts.insert("Maleakhi", 4);
+    let segments = references[0].segments(&chapter_counts).unwrap();
+    let chapters: Vec<(&str, i32)> = segments.iter().map(|s| (s.book, s.chapter)).collect();
+    assert_eq!(chapters, vec![("Maleakhi", 4), ("Matius", 1)]);
+}
+
+#[test]
+fn test_parse_chapter_span_and_reversed_span() {
+    let references = parse_references("Hakim-hakim 1-2").unwrap();
+    assert_eq!(references[0].to_string(), "Hakim-hakim 1-2");
+    assert_eq!(references[0].segments(&HashMap::new()).unwrap().len(), 2);
+
+    assert!(parse_references("Mat 1:1 - Mal 4:5").is_err());
+    assert!(parse_references("Kej 2:3-1:26").is_err());
+}
\ No newline at end of file
@@ -0,0 +1,90 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::Duration;
+use alkitab_api_rust::config::RetryConfig;
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::services::retry::{AttemptError, RetryPolicy};
+
+fn policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy::from_config(&RetryConfig {
+        max_attempts,
+        base_delay_ms: 1,
+        max_delay_ms: 5,
+        ..RetryConfig::default()
+    })
+}
+
+#[tokio::test]
+async fn test_transient_errors_are_retried_until_success() {
+    let attempts = AtomicU32::new(0);
+    let result = policy(3).run(|| async {
+        if attempts.fetch_add(1, Ordering::SeqCst) < 2 {
+            Err(AttemptError::Transient(AppError::ExternalService("HTTP 502".to_string())))
+        } else {
+            Ok("Kejadian 1")
+        }
+    }).await;
+
+    assert_eq!(result.unwrap(), "Kejadian 1");
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_exhausted_retries_report_attempt_count() {
+    let attempts = AtomicU32::new(0);
+    let result: Result<(), AppError> = policy(3).run(|| async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err(AttemptError::Transient(AppError::ExternalService("Failed to fetch chapter: HTTP 503".to_string())))
+    }).await;
+
+    match result {
+        Err(AppError::ExternalService(msg)) => assert_eq!(msg, "Failed to fetch chapter: HTTP 503 (after 3 attempts)"),
+        other => panic!("Expected ExternalService, got {:?}", other),
+    }
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_permanent_errors_are_not_retried() {
+    let attempts = AtomicU32::new(0);
+    let result: Result<(), AppError> = policy(3).run(|| async {
+        attempts.fetch_add(1, Ordering::SeqCst);
+        Err(AttemptError::Permanent(AppError::ExternalService("Failed to fetch chapter: HTTP 404".to_string())))
+    }).await;
+
+    // A single attempt keeps the original message
+    match result {
+        Err(AppError::ExternalService(msg)) => assert_eq!(msg, "Failed to fetch chapter: HTTP 404"),
+        other => panic!("Expected ExternalService, got {:?}", other),
+    }
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn test_backoff_grows_exponentially_up_to_cap_with_jitter() {
+    let exact = RetryPolicy::from_config(&RetryConfig {
+        base_delay_ms: 100,
+        max_delay_ms: 1000,
+        jitter: 0.0,
+        ..RetryConfig::default()
+    });
+    assert_eq!(exact.backoff(1), Duration::from_millis(100));
+    assert_eq!(exact.backoff(2), Duration::from_millis(200));
+    assert_eq!(exact.backoff(4), Duration::from_millis(800));
+    assert_eq!(exact.backoff(5), Duration::from_millis(1000));
+    assert_eq!(exact.backoff(40), Duration::from_millis(1000));
+
+    let jittered = RetryPolicy { jitter: 0.5, ..exact };
+    for _ in 0..50 {
+        let delay = jittered.backoff(3);
+        assert!(delay > Duration::from_millis(200) && delay <= Duration::from_millis(400), "{:?}", delay);
+    }
+}
+
+#[test]
+fn test_retryable_statuses_come_from_config() {
+    let policy = RetryPolicy::default();
+    assert!(policy.is_retryable_status(502));
+    assert!(policy.is_retryable_status(429));
+    assert!(!policy.is_retryable_status(404));
+    assert_eq!(RetryPolicy::none().max_attempts, 1);
+}
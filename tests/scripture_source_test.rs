@@ -0,0 +1,63 @@
+use std::sync::Arc;
+use std::time::Duration;
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::models::reference::{parse_references, MAX_PASSAGE_CHAPTERS};
+use alkitab_api_rust::services::bible::{BibleService, PASSAGE_FETCH_CONCURRENCY};
+
+mod common;
+
+use common::{StubSource, StubVerses};
+
+fn service() -> BibleService {
+    BibleService::with_source(Arc::new(StubSource::new(&["tb"]).with_chapters(3).with_verses(StubVerses::Fixed(10))))
+}
+
+#[tokio::test]
+async fn test_find_book_metadata_from_versification_table() {
+    // The stub lists three chapters, but metadata no longer asks the source
+    let metadata = service().find_book_metadata("Genesis", "tb").unwrap();
+    assert_eq!(metadata.book, "Kejadian");
+    assert_eq!(metadata.verses, (1..=50).collect::<Vec<i32>>());
+}
+
+#[tokio::test]
+async fn test_get_chapter_resolves_book_names() {
+    let chapter = service().get_chapter("kej", "2", "").await.unwrap();
+    assert_eq!(chapter.book, vec!["Kejadian"]);
+    assert_eq!(chapter.chapter, 2);
+    assert_eq!(chapter.version.as_deref(), Some("tb"));
+}
+
+#[tokio::test]
+async fn test_get_passage_across_books() {
+    // Yudas has a single chapter in the versification table, so the range runs on into Wahyu
+    let references = parse_references("Yud 1:9 - Wahyu 1:2").unwrap();
+    let passages = service().get_passages(&references, "tb").await.unwrap();
+    let chapters = &passages[0].chapters;
+    assert_eq!(chapters.len(), 2);
+    assert_eq!(chapters[0].book, vec!["Yudas"]);
+    assert_eq!(chapters[0].verses.iter().map(|v| v.verse).collect::<Vec<_>>(), vec![9, 10]);
+    assert_eq!(chapters[0].title, vec!["Yudas 1:9-10"]);
+    assert_eq!(chapters[1].book, vec!["Wahyu"]);
+    assert_eq!(chapters[1].total_verses, 2);
+}
+
+#[tokio::test]
+async fn test_passage_requests_are_capped_and_fetched_a_few_at_a_time() {
+    let source = Arc::new(StubSource::new(&["tb"]).with_delay(Duration::from_millis(10)));
+    let service = BibleService::with_source(source.clone());
+
+    let psalms: Vec<String> = (1..=20).map(|psalm| format!("Mzm {}", psalm)).collect();
+    let passages = service.get_passages(&parse_references(&psalms.join("; ")).unwrap(), "tb").await.unwrap();
+    assert_eq!(passages.iter().map(|p| p.chapters[0].chapter).collect::<Vec<_>>(), (1..=20).collect::<Vec<_>>());
+    assert_eq!(source.fetch_count(), 20);
+    assert!(source.peak_in_flight() <= PASSAGE_FETCH_CONCURRENCY, "{} fetches at once", source.peak_in_flight());
+
+    // Each reference is within the limit, but together they are not
+    let references = parse_references("Kej 1-30; Kel 1-30").unwrap();
+    match service.get_passages(&references, "tb").await {
+        Err(AppError::InvalidInput(msg)) => assert_eq!(msg, format!("References span more than {} chapters", MAX_PASSAGE_CHAPTERS)),
+        other => panic!("Expected InvalidInput, got {:?}", other.map(|p| p.len())),
+    }
+    assert_eq!(source.fetch_count(), 20);
+}
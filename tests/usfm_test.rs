@@ -0,0 +1,79 @@
+use alkitab_api_rust::import::usfm::parse_usfm;
+use alkitab_api_rust::import::ImportErrorKind;
+
+const RUTH: &str = r#"\id RUT Alkitab Terjemahan Baru
+\h Rut
+\toc1 Rut
+\mt1 Rut
+\c 1
+\s1 Elimelekh dan keluarganya pindah ke Moab
+\p
+\v 1 Pada zaman para hakim memerintah ada kelaparan di tanah Israel.\f + \fr 1:1 \ft Catatan kaki.\f*
+\v 2 Nama orang itu ialah \w Elimelekh|strong="H458"\w*, nama isterinya Naomi.
+\q1
+\v 3 Kemudian matilah Elimelekh,
+\q2 suami Naomi.\x - \xo 1:3 \xt Kej 12:10\x*
+\s1 Rut ikut Naomi ke Betlehem
+\p
+\v 4 Keduanya mengambil perempuan Moab.
+\c 2
+\p
+\v 1 Naomi mempunyai seorang sanak.
+\v 2 Maka Rut berkata.
+"#;
+
+#[test]
+fn test_parse_usfm_chapters_and_titles() {
+    let chapters = parse_usfm(RUTH, "rut.usfm", Some("Alkitab Terjemahan Baru (TB)")).unwrap();
+    assert_eq!(chapters.len(), 2);
+
+    let first = &chapters[0];
+    assert_eq!(first.book, vec!["Rut"]);
+    assert_eq!(first.chapter, 1);
+    assert_eq!(first.total_verses, 4);
+    assert_eq!(first.version.as_deref(), Some("Alkitab Terjemahan Baru (TB)"));
+    assert_eq!(first.title, vec![
+        "Elimelekh dan keluarganya pindah ke Moab (1-3)".to_string(),
+        "Rut ikut Naomi ke Betlehem (4-4)".to_string(),
+    ]);
+    assert_eq!(first.verses[0].content, "Pada zaman para hakim memerintah ada kelaparan di tanah Israel.");
+    assert_eq!(first.verses[1].content, "Nama orang itu ialah Elimelekh, nama isterinya Naomi.");
+    assert_eq!(first.verses[2].content, "Kemudian matilah Elimelekh, suami Naomi.");
+
+    // Chapters without section headings are left untitled
+    assert!(chapters[1].title.is_empty());
+    assert_eq!(chapters[1].total_verses, 2);
+}
+
+#[test]
+fn test_parse_usfm_errors() {
+    let error = parse_usfm("\\id XYZ\n\\c 1\n\\v 1 text", "xyz.usfm", None).unwrap_err();
+    assert_eq!(error.kind, ImportErrorKind::UnknownBook("XYZ".to_string()));
+    assert_eq!(error.line, 1);
+
+    let error = parse_usfm("\\id GEN\n\\c satu\n\\v 1 text", "gen.usfm", None).unwrap_err();
+    assert_eq!(error.kind, ImportErrorKind::InvalidChapter("satu".to_string()));
+    assert_eq!(error.line, 2);
+
+    let error = parse_usfm("\\id GEN\n\\c 1\n\\v 1 a\n\\v 1 b\n\\v 2 c", "gen.usfm", None).unwrap_err();
+    assert!(matches!(error.kind, ImportErrorKind::DuplicateVerse(_)));
+    assert_eq!(error.to_string(), "gen.usfm:5: duplicate verse Kejadian 1:1");
+}
+
+#[tokio::test]
+async fn test_local_corpus_from_usfm_directory() {
+    use alkitab_api_rust::import::CorpusFormat;
+    use alkitab_api_rust::services::local_corpus::LocalCorpusSource;
+    use alkitab_api_rust::services::source::ScriptureSource;
+
+    let dir = std::env::temp_dir().join(format!("alkitab-usfm-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("08-RUT.usfm"), RUTH).unwrap();
+    std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+
+    let source = LocalCorpusSource::load_format("tb", &dir, CorpusFormat::Usfm, None).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    assert_eq!(source.fetch_chapter("Rut", 1, "tb").await.unwrap().chapter, 1);
+    assert_eq!(source.fetch_chapter("Rut", 2, "tb").await.unwrap().total_verses, 2);
+}
@@ -0,0 +1,62 @@
+use alkitab_api_rust::models::bible::BibleChapter;
+use alkitab_api_rust::models::verse_selection::VerseSelection;
+use alkitab_api_rust::services::bible::BibleService;
+use alkitab_api_rust::error::AppError;
+
+mod common;
+
+use common::chapter;
+
+fn sample_chapter() -> BibleChapter {
+    BibleChapter {
+        title: vec![
+            "Percakapan dengan Nikodemus (1-13)".to_string(),
+            "Kasih Allah (14-21)".to_string(),
+        ],
+        version: Some("Alkitab Terjemahan Baru (TB)".to_string()),
+        ..chapter("Yohanes", 3, 1..=21)
+    }
+}
+
+#[test]
+fn test_parse_verse_selection() {
+    let selection = VerseSelection::parse("1-3, 7,10–12").unwrap();
+    assert_eq!(selection.to_string(), "1-3,7,10-12");
+    assert!(selection.contains(2));
+    assert!(selection.contains(11));
+    assert!(!selection.contains(5));
+    assert_eq!(selection.verses().count(), 7);
+}
+
+#[test]
+fn test_parse_invalid_verse_selection() {
+    for input in ["", "abc", "0", "5-3", "1-2-3", "4,"] {
+        match VerseSelection::parse(input) {
+            Err(AppError::InvalidInput(_)) => {}
+            other => panic!("Expected InvalidInput for '{}', got {:?}", input, other),
+        }
+    }
+}
+
+#[test]
+fn test_select_verses_trims_titles() {
+    let service = BibleService::new();
+    let selection = VerseSelection::parse("12-16").unwrap();
+    let chapter = service.select_verses(sample_chapter(), &selection).unwrap();
+    assert_eq!(chapter.total_verses, 5);
+    assert_eq!(chapter.verses.first().unwrap().verse, 12);
+    assert_eq!(chapter.title, vec![
+        "Percakapan dengan Nikodemus (12-13)".to_string(),
+        "Kasih Allah (14-16)".to_string(),
+    ]);
+}
+
+#[test]
+fn test_select_verses_missing_verse() {
+    let service = BibleService::new();
+    let selection = VerseSelection::parse("20-22").unwrap();
+    match service.select_verses(sample_chapter(), &selection) {
+        Err(AppError::NotFound(_)) => {}
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::models::bible::BibleChapter;
+use alkitab_api_rust::models::reference::parse_references;
+use alkitab_api_rust::models::verse_selection::VerseSelection;
+use alkitab_api_rust::models::versification::{map_selection, map_verse, verse_count, Scheme};
+use alkitab_api_rust::services::bible::BibleService;
+
+mod common;
+
+use common::{StubSource, StubVerses};
+
+/// Serves every chapter with as many verses as the version's numbering gives it
+fn service() -> BibleService {
+    BibleService::with_source(Arc::new(StubSource::new(&["tb", "kjv"]).with_verses(StubVerses::Versified)))
+}
+
+fn verse_numbers(chapter: &BibleChapter) -> Vec<i32> {
+    chapter.verses.iter().map(|v| v.verse).collect()
+}
+
+#[test]
+fn test_psalm_superscriptions_shift_verses() {
+    assert_eq!(verse_count("Mazmur", 51, "tb"), Some(21));
+    assert_eq!(verse_count("Mazmur", 51, "kjv"), Some(19));
+    assert_eq!(map_verse("Mazmur", 51, 3, Scheme::Tb, Scheme::Kjv), Some((1, 1)));
+    assert_eq!(map_verse("Mazmur", 51, 2, Scheme::Tb, Scheme::Kjv), None);
+    assert_eq!(map_verse("Mazmur", 51, 19, Scheme::Kjv, Scheme::Tb), Some((21, 21)));
+    // Psalms without a numbered superscription line up
+    assert_eq!(map_verse("Mazmur", 23, 1, Scheme::Tb, Scheme::Kjv), Some((1, 1)));
+    // The KJV's last two verses of Psalm 13 are one verse in the TB
+    assert_eq!(map_verse("Mazmur", 13, 6, Scheme::Tb, Scheme::Kjv), Some((5, 6)));
+    assert_eq!(map_verse("Mazmur", 13, 6, Scheme::Kjv, Scheme::Tb), Some((6, 6)));
+}
+
+#[test]
+fn test_new_testament_splits_and_merges() {
+    assert_eq!(map_verse("2 Korintus", 13, 12, Scheme::Tb, Scheme::Kjv), Some((12, 13)));
+    assert_eq!(map_verse("2 Korintus", 13, 13, Scheme::Tb, Scheme::Kjv), Some((14, 14)));
+    assert_eq!(map_verse("2 Korintus", 13, 13, Scheme::Kjv, Scheme::Tb), Some((12, 12)));
+    assert_eq!(map_verse("3 Yohanes", 1, 14, Scheme::Kjv, Scheme::Tb), Some((14, 15)));
+    assert_eq!(map_verse("Wahyu", 12, 18, Scheme::Tb, Scheme::Kjv), None);
+    // Maleakhi and Roma 16 are numbered alike
+    assert_eq!(map_verse("Maleakhi", 4, 6, Scheme::Tb, Scheme::Kjv), Some((6, 6)));
+    assert_eq!(map_verse("Roma", 16, 27, Scheme::Tb, Scheme::Kjv), Some((27, 27)));
+}
+
+#[test]
+fn test_map_selection() {
+    let selection = VerseSelection::parse("1-4").unwrap();
+    assert_eq!(map_selection("Mazmur", 51, &selection, Scheme::Tb, Scheme::Kjv), Some(VerseSelection::range(1, 2)));
+    assert_eq!(map_selection("Mazmur", 51, &VerseSelection::parse("1-2").unwrap(), Scheme::Tb, Scheme::Kjv), None);
+    assert_eq!(map_selection("Mazmur", 51, &selection, Scheme::Tb, Scheme::Tb), Some(selection));
+    assert_eq!(Scheme::of_version("ayt"), Scheme::Tb);
+    assert_eq!(Scheme::of_version("niv"), Scheme::Kjv);
+}
+
+#[tokio::test]
+async fn test_tb_references_are_renumbered_for_other_versions() {
+    let service = service();
+    let selection = VerseSelection::parse("3-4").unwrap();
+
+    let tb = service.get_verses("Mazmur", "51", &selection, "tb").await.unwrap();
+    assert_eq!(verse_numbers(&tb), vec![3, 4]);
+    let kjv = service.get_verses("Mazmur", "51", &selection, "kjv").await.unwrap();
+    assert_eq!(verse_numbers(&kjv), vec![1, 2]);
+
+    match service.get_verses("Mazmur", "51", &VerseSelection::parse("1-2").unwrap(), "kjv").await {
+        Err(AppError::NotFound(msg)) => assert_eq!(msg, "Mazmur 51:1-2 has no counterpart in kjv"),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_passages_are_renumbered_for_other_versions() {
+    let service = service();
+    let references = parse_references("Mzm 51:20-52:3").unwrap();
+    let passages = service.get_passages(&references, "kjv").await.unwrap();
+    let chapters = &passages[0].chapters;
+    assert_eq!(verse_numbers(&chapters[0]), vec![18, 19]);
+    assert_eq!(verse_numbers(&chapters[1]), vec![1]);
+}
+
+#[tokio::test]
+async fn test_passage_spans_check_their_start_verse() {
+    let service = service();
+    match service.get_passages(&parse_references("Kej 1:40-2:3").unwrap(), "tb").await {
+        Err(AppError::NotFound(msg)) => assert_eq!(msg, "Verse 40 not found in Kejadian 1"),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+
+    // A superscription the KJV leaves unnumbered starts the span at its first verse
+    let passages = service.get_passages(&parse_references("Mzm 51:2-52:3").unwrap(), "kjv").await.unwrap();
+    assert_eq!(verse_numbers(&passages[0].chapters[0]), (1..=19).collect::<Vec<_>>());
+
+    // The TB's last verse of Wahyu 12 is the KJV's first of Wahyu 13
+    match service.get_passages(&parse_references("Why 12:18-13:1").unwrap(), "kjv").await {
+        Err(AppError::NotFound(msg)) => assert_eq!(msg, "Wahyu 12:18 has no counterpart in kjv"),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_huge_ranges_are_rejected_before_renumbering() {
+    // Ranges are mapped through their ends, not verse by verse
+    let huge = VerseSelection::range(1, 2_000_000_000);
+    assert_eq!(map_selection("Kejadian", 1, &huge, Scheme::Tb, Scheme::Kjv), Some(VerseSelection::range(1, 31)));
+    assert_eq!(map_selection("Mazmur", 51, &huge, Scheme::Kjv, Scheme::Tb), Some(VerseSelection::range(3, 21)));
+
+    let service = service();
+    let references = parse_references("Kej 1:1-2000000000").unwrap();
+    match service.get_passages(&references, "kjv").await {
+        Err(AppError::NotFound(msg)) => assert_eq!(msg, "Verse 32 not found in Kejadian 1"),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+    match service.get_verses("Kejadian", "1", &huge, "kjv").await {
+        Err(AppError::NotFound(msg)) => assert_eq!(msg, "Verse 32 not found in Kejadian 1"),
+        other => panic!("Expected NotFound, got {:?}", other),
+    }
+}
@@ -0,0 +1,50 @@
+use alkitab_api_rust::error::AppError;
+use alkitab_api_rust::models::bible::Testament;
+use alkitab_api_rust::models::versions::describe_version;
+
+mod common;
+
+use common::fixture_service;
+
+#[test]
+fn test_versions_catalogue() {
+    let versions = fixture_service().versions();
+    let codes: Vec<&str> = versions.iter().map(|v| v.code.as_str()).collect();
+    assert_eq!(codes, vec!["ayt", "esv", "kjv", "nasb", "niv", "nkjv", "nlt", "tb"]);
+
+    let tb = versions.iter().find(|v| v.code == "tb").unwrap();
+    assert_eq!(tb.name, "Alkitab Terjemahan Baru (TB)");
+    assert_eq!(tb.language.as_deref(), Some("id"));
+    assert_eq!(tb.testaments, vec![Testament::Old, Testament::New]);
+    assert!(tb.section_titles);
+
+    let esv = versions.iter().find(|v| v.code == "esv").unwrap();
+    assert_eq!(esv.language.as_deref(), Some("en"));
+    assert!(!esv.section_titles);
+}
+
+#[test]
+fn test_configured_versions_are_described_by_code() {
+    let local = describe_version("bis");
+    assert_eq!(local.name, "BIS");
+    assert_eq!(local.language, None);
+    assert!(local.testaments.is_empty());
+}
+
+#[tokio::test]
+async fn test_unknown_version_is_rejected_with_valid_codes() {
+    let service = fixture_service();
+    match service.get_chapter("Kejadian", "1", "xyz").await {
+        Err(AppError::InvalidInput(msg)) => {
+            assert_eq!(msg, "Unknown version 'xyz', expected one of: ayt, esv, kjv, nasb, niv, nkjv, nlt, tb");
+        }
+        other => panic!("Expected InvalidInput, got {:?}", other),
+    }
+    assert!(matches!(service.compare("Kejadian", "1", &["tb".to_string(), "xyz".to_string()]).await, Err(AppError::InvalidInput(_))));
+    assert!(matches!(service.book_metadata("Kejadian", "xyz"), Err(AppError::InvalidInput(_))));
+
+    // Codes are matched regardless of case
+    assert_eq!(service.resolve_version("TB").unwrap(), "tb");
+    let chapter = service.get_chapter("Kejadian", "1", "TB").await.unwrap();
+    assert_eq!(chapter.version.as_deref(), Some("Alkitab Terjemahan Baru (TB)"));
+}
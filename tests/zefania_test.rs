@@ -0,0 +1,57 @@
+use alkitab_api_rust::import::zefania::parse_zefania;
+use alkitab_api_rust::import::ImportErrorKind;
+
+const JOHN: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<XMLBIBLE biblename="King James Version" type="x-bible">
+  <INFORMATION><title>King James Version</title></INFORMATION>
+  <BIBLEBOOK bnumber="43" bname="John" bsname="Joh">
+    <CHAPTER cnumber="3">
+      <CAPTION>Jesus and Nicodemus</CAPTION>
+      <VERS vnumber="1">There was a man of the Pharisees, named <STYLE css="font-style:italic">Nicodemus</STYLE>, a ruler of the Jews:</VERS>
+      <VERS vnumber="2">The same came to Jesus by night,<BR/>and said unto him.<NOTE type="x-studynote">Or, Rabbi.</NOTE></VERS>
+    </CHAPTER>
+  </BIBLEBOOK>
+  <BIBLEBOOK bnumber="19" bname="Mazmur">
+    <CHAPTER cnumber="23">
+      <VERS vnumber="1">TUHAN adalah gembalaku &amp; takkan kekurangan aku.</VERS>
+    </CHAPTER>
+  </BIBLEBOOK>
+</XMLBIBLE>
+"#;
+
+#[test]
+fn test_parse_zefania_books_and_captions() {
+    let chapters = parse_zefania(JOHN, "kjv.xml", None).unwrap();
+    assert_eq!(chapters.len(), 2);
+
+    let john = &chapters[0];
+    assert_eq!(john.book, vec!["Yohanes"]);
+    assert_eq!(john.chapter, 3);
+    assert_eq!(john.version.as_deref(), Some("King James Version"));
+    assert_eq!(john.title, vec!["Jesus and Nicodemus (1-2)".to_string()]);
+    assert_eq!(john.verses[0].content, "There was a man of the Pharisees, named Nicodemus, a ruler of the Jews:");
+    assert_eq!(john.verses[1].content, "The same came to Jesus by night, and said unto him.");
+
+    assert_eq!(chapters[1].book, vec!["Mazmur"]);
+    assert_eq!(chapters[1].verses[0].content, "TUHAN adalah gembalaku & takkan kekurangan aku.");
+}
+
+#[test]
+fn test_parse_zefania_errors() {
+    let error = parse_zefania("<XMLBIBLE>\n<BIBLEBOOK bname=\"Xyz\"></BIBLEBOOK></XMLBIBLE>", "x.xml", None).unwrap_err();
+    assert_eq!(error.kind, ImportErrorKind::UnknownBook("Xyz".to_string()));
+    assert_eq!(error.line, 2);
+
+    // An unrecognised name still resolves through the canonical book number
+    let chapters = parse_zefania(r#"<XMLBIBLE><BIBLEBOOK bnumber="1" bname="Xyz"><CHAPTER cnumber="1"><VERS vnumber="1">a</VERS></CHAPTER></BIBLEBOOK></XMLBIBLE>"#, "x.xml", None).unwrap();
+    assert_eq!(chapters[0].book, vec!["Kejadian"]);
+    // and wins over a name that matches another book
+    let chapters = parse_zefania(r#"<XMLBIBLE><BIBLEBOOK bnumber="40" bname="Mark"><CHAPTER cnumber="1"><VERS vnumber="1">a</VERS></CHAPTER></BIBLEBOOK></XMLBIBLE>"#, "x.xml", None).unwrap();
+    assert_eq!(chapters[0].book, vec!["Matius"]);
+
+    let error = parse_zefania(r#"<XMLBIBLE><BIBLEBOOK bname="Genesis"><CHAPTER cnumber="I"></CHAPTER></BIBLEBOOK></XMLBIBLE>"#, "gen.xml", None).unwrap_err();
+    assert_eq!(error.kind, ImportErrorKind::InvalidChapter("I".to_string()));
+
+    let error = parse_zefania("<XMLBIBLE><BIBLEBOOK bname=\"Genesis\"><CHAPTER cnumber=\"1\">\n<VERS vnumber=\"1\">a</VERS>\n<VERS vnumber=\"1\">b</VERS></CHAPTER></BIBLEBOOK></XMLBIBLE>", "gen.xml", None).unwrap_err();
+    assert_eq!(error.to_string(), "gen.xml:3: duplicate verse Kejadian 1:1");
+}